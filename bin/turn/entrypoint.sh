@@ -7,5 +7,8 @@ if [ $TURN_NATS ]; then COMMAND="${COMMAND} --nats ${TURN_NATS}"; fi
 if [ $TURN_BUFFER ]; then COMMAND="${COMMAND} --buffer ${TURN_BUFFER}"; fi
 if [ $TURN_THREADS ]; then COMMAND="${COMMAND} --threads ${TURN_THREADS}"; fi
 if [ $TURN_EXTERNAL ]; then COMMAND="${COMMAND} --external ${TURN_EXTERNAL}"; fi
+if [ $TURN_TLS_LISTEN ]; then COMMAND="${COMMAND} --tls-listen ${TURN_TLS_LISTEN}"; fi
+if [ $TURN_TLS_CERT ]; then COMMAND="${COMMAND} --tls-cert ${TURN_TLS_CERT}"; fi
+if [ $TURN_TLS_KEY ]; then COMMAND="${COMMAND} --tls-key ${TURN_TLS_KEY}"; fi
 
 /bin/bash -c "${COMMAND}"
@@ -1,6 +1,7 @@
 use clap::Clap;
 use std::{
     net::SocketAddr,
+    path::PathBuf,
     sync::Arc
 };
 
@@ -35,6 +36,28 @@ pub struct Argv {
     #[clap(default_value = "127.0.0.1:3478")]
     #[clap(about = "service bind address and port")]
     pub listen: SocketAddr,
+    /// the address and port bound by TLS Server (turns:). 
+    /// many enterprise networks only allow 443/TLS egress, 
+    /// the TLS server shares the allocations with the UDP 
+    /// server, it is disabled by default and requires the 
+    /// certificate chain and the private key (PEM).
+    #[clap(long)]
+    #[clap(about = "tls service bind address and port")]
+    pub tls_listen: Option<SocketAddr>,
+    /// the address and port bound by DTLS Server. 
+    /// for the networks where the plain UDP STUN messages 
+    /// are blocked by DPI, the DTLS server shares the 
+    /// allocations with the UDP server, it is disabled by 
+    /// default and uses the TLS certificate and private key.
+    #[clap(long)]
+    #[clap(about = "dtls service bind address and port")]
+    pub dtls_listen: Option<SocketAddr>,
+    #[clap(long)]
+    #[clap(about = "tls certificate chain file")]
+    pub tls_cert: Option<PathBuf>,
+    #[clap(long)]
+    #[clap(about = "tls private key file")]
+    pub tls_key: Option<PathBuf>,
     /// specify the remote control service. 
     /// the control service is very important. 
     /// if it is separated from it, 
@@ -63,6 +86,21 @@ pub struct Argv {
     #[clap(long)]
     #[clap(about = "runtime threads size")]
     pub threads: Option<usize>,
+    /// the maximum number of allocations active at one time 
+    /// for a given username, new allocations exceeding it are 
+    /// rejected with a 486 (Allocation Quota Reached). 
+    /// it is not limited by default.
+    #[clap(long)]
+    #[clap(about = "allocations quota of each user")]
+    pub quota: Option<usize>,
+    /// the relayed bandwidth (byte/s) of each allocation, 
+    /// the datagrams exceeding it are discarded, so that one 
+    /// participant cannot saturate the node's uplink. the 
+    /// control service can override it for each user. 
+    /// it is not limited by default.
+    #[clap(long)]
+    #[clap(about = "relayed bandwidth of each allocation")]
+    pub bandwidth: Option<u64>,
 }
 
 impl Argv {
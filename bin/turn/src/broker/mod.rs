@@ -2,6 +2,7 @@ pub mod request;
 pub mod response;
 
 use super::argv::Argv;
+use super::state::State;
 use response::Response;
 use anyhow::Result;
 use std::{
@@ -20,7 +21,8 @@ use std::convert::{
 };
 
 struct Topic {
-    auth: String
+    auth: String,
+    allocations: String
 }
 
 /// Broker
@@ -40,7 +42,8 @@ impl Broker {
         Ok(Arc::new(Self { 
             nats: connect(c.nats.as_str()).await?,
             topic: Topic {
-                auth: format!("auth.{}", c.realm)
+                auth: format!("auth.{}", c.realm),
+                allocations: format!("allocations.{}", c.realm)
             }
         }))
     }
@@ -62,4 +65,31 @@ impl Broker {
         let message = self.nats.request(&self.topic.auth, Into::<Vec<u8>>::into(req)).await?;
         Response::<response::Auth>::try_from(message.data.as_slice())?.into_result()
     }
+
+    /// answer the control service with the allocations 
+    /// count of each user of the node.
+    ///
+    /// a failed reply is logged and does not end the
+    /// subscription, the next query is answered as usual.
+    ///
+    /// ```no_run
+    /// let c = argv::Argv::generate()?;
+    /// let broker = Broker::new(&c).await?;
+    /// let state = State::new(&c, &broker);
+    /// broker.serve(state).await?;
+    /// ```
+    #[rustfmt::skip]
+    pub async fn serve(self: Arc<Self>, s: Arc<State>) -> Result<()> {
+        let sub = self.nats.subscribe(&self.topic.allocations).await?;
+        while let Some(message) = sub.next().await {
+            let allocations = s.get_allocations().await;
+            let res = request::Allocations { allocations };
+            if let Err(e) = message.respond(Vec::from(res)).await {
+                log::error!("allocations reply error: {}", e);
+                continue;
+            }
+        }
+
+        Ok(())
+    }
 }
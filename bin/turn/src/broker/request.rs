@@ -1,7 +1,7 @@
 use serde::Serialize;
 use std::{
-    net::SocketAddr,
-    convert::Into
+    collections::HashMap,
+    net::SocketAddr
 };
 
 /// auth request struct.
@@ -11,18 +11,41 @@ pub struct Auth {
     pub username: String
 }
 
-impl Into<Vec<u8>> for Auth {
+impl From<Auth> for Vec<u8> {
     /// uncheck input serialization.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// Into::<Auth>::into(Auth {
+    /// Vec::from(Auth {
     ///     addr: "127.0.0.1:8080".parse().unwrap(),
     ///     username: "panda".to_string()
     /// })
     /// ```
-    fn into(self) -> Vec<u8> {
-        serde_json::to_vec(&self).unwrap()
+    fn from(value: Auth) -> Self {
+        serde_json::to_vec(&value).unwrap()
+    }
+}
+
+/// allocations reply struct.
+///
+/// the allocations count of each user.
+#[derive(Serialize)]
+pub struct Allocations {
+    pub allocations: HashMap<String, usize>
+}
+
+impl From<Allocations> for Vec<u8> {
+    /// uncheck input serialization.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// Vec::from(Allocations {
+    ///     allocations: HashMap::new()
+    /// })
+    /// ```
+    fn from(value: Allocations) -> Self {
+        serde_json::to_vec(&value).unwrap()
     }
 }
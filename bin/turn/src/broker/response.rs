@@ -1,15 +1,28 @@
 use serde::Deserialize;
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    net::SocketAddr
+};
+
 use anyhow::{
     Result,
     anyhow
 };
 
 /// auth response struct.
+///
+/// the control service can redirect the node to an
+/// alternate server, for example when this node is
+/// draining or over capacity.
+///
+/// the bandwidth (byte/s) overrides the relayed 
+/// bandwidth of the node for this user.
 #[derive(Deserialize)]
 pub struct Auth {
     pub password: String,
     pub group: u32,
+    pub alternate: Option<SocketAddr>,
+    pub bandwidth: Option<u64>,
 }
 
 /// response from nats request.
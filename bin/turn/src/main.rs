@@ -19,6 +19,7 @@ async fn main() -> Result<()> {
     let c = Argv::new();
     let b = Broker::new(&c).await?;
     let s = State::new(&c, &b);
+    tokio::spawn(b.serve(s.clone()));
     server::run(c, s.clone()).await?;
     s.run().await?;
     Ok(())
@@ -1,8 +1,11 @@
 use anyhow::Result;
 use bytes::BytesMut;
 use super::{ 
+    relay,
     Context, 
-    Response
+    Response,
+    DEFAULT_LIFETIME,
+    MAX_LIFETIME
 };
 
 use std::{
@@ -17,24 +20,35 @@ use stun::{
 };
 
 use stun::attribute::{
+    FAMILY_IPV4,
     Error,
     ErrKind,
     ErrorCode,
     Realm,
     Nonce,
     ReqeestedTransport,
+    RequestedAddressFamily,
+    ReservationToken,
+    AlternateServer,
+    EvenPort,
     XorMappedAddress,
     XorRelayedAddress,
     ResponseOrigin,
-    Lifetime,
-    UserName
+    Lifetime
 };
 
 use stun::attribute::ErrKind::{
-    Unauthorized,
-    ServerError
+    AllocationMismatch,
+    AddressFamilyNotSupported,
+    TryAlternate,
+    BadRequest,
+    InsufficientCapacity,
+    UnsupportedTransportAddress
 };
 
+/// the protocol number of UDP.
+const UDP: u8 = 17;
+
 /// return allocate error response
 #[inline(always)]
 async fn reject<'a>(
@@ -49,7 +63,35 @@ async fn reject<'a>(
     pack.append::<Realm>(&ctx.conf.realm);
     pack.append::<Nonce>(&nonce);
     pack.try_into(None)?;
-    Ok(Some((w, ctx.addr)))
+    Ok(Some((w, ctx.addr, ctx.transport)))
+}
+
+/// return allocate redirect response
+///
+/// the 300 (Try Alternate) error response is authenticated
+/// with the key of the request, so that the client can trust
+/// the ALTERNATE-SERVER attribute.
+#[inline(always)]
+fn redirect<'a>(
+    ctx: &Context,
+    m: &MessageReader<'a>,
+    p: &[u8; 16],
+    alternate: SocketAddr,
+    w: &'a mut BytesMut,
+) -> Result<Response<'a>> {
+    let mut pack = MessageWriter::derive(Kind::AllocateError, m, w);
+    pack.append::<ErrorCode>(Error::from(TryAlternate));
+    pack.append::<AlternateServer>(alternate);
+    pack.try_into(Some(p))?;
+    Ok(Some((w, ctx.addr.clone(), ctx.transport)))
+}
+
+/// return the cached allocate ok response
+#[inline(always)]
+fn replay(ctx: Context, buf: Vec<u8>, w: &mut BytesMut) -> Result<Response<'_>> {
+    w.clear();
+    w.extend_from_slice(&buf);
+    Ok(Some((w, ctx.addr, ctx.transport)))
 }
 
 /// return allocate ok response
@@ -68,6 +110,8 @@ async fn resolve<'a>(
     m: &MessageReader<'a>,
     p: &[u8; 16],
     port: u16,
+    token: Option<u64>,
+    lifetime: u32,
     w: &'a mut BytesMut,
 ) -> Result<Response<'a>> {
     let alloc_addr = Arc::new(SocketAddr::new(ctx.conf.external.ip(), port));
@@ -75,15 +119,32 @@ async fn resolve<'a>(
     pack.append::<XorRelayedAddress>(*alloc_addr.as_ref());
     pack.append::<XorMappedAddress>(*ctx.addr.as_ref());
     pack.append::<ResponseOrigin>(ctx.conf.external);
-    pack.append::<Lifetime>(600);
+    pack.append::<Lifetime>(lifetime);
+    if let Some(t) = token {
+        pack.append::<ReservationToken>(t);
+    }
+
     pack.try_into(Some(p))?;
-    Ok(Some((w, ctx.addr.clone())))
+    ctx.state.set_transaction(&ctx.addr, w).await;
+    Ok(Some((w, ctx.addr.clone(), ctx.transport)))
 }
 
 /// process allocate request
 ///
 /// [rfc8489](https://tools.ietf.org/html/rfc8489)
 ///
+/// The server checks if the 5-tuple is currently in use by an existing
+/// allocation.  If yes, the server rejects the request with a 437
+/// (Allocation Mismatch) error.
+///
+/// When UDP or DTLS-over-UDP transport is used between the client and
+/// the server, the client will retransmit a request if it does not
+/// receive a response within a certain timeout period.  Because of this,
+/// the server may receive two (or more) requests with the same 5-tuple
+/// and same transaction id, the success response of the request that
+/// created the allocation is remembered for 40 seconds and sent again
+/// to such a retransmission.
+///
 /// In all cases, the server SHOULD only allocate ports from the range
 /// 49152 - 65535 (the Dynamic and/or Private Port range [PORT-NUMBERS]),
 /// unless the TURN server application knows, through some means not
@@ -96,36 +157,137 @@ async fn resolve<'a>(
 /// server SHOULD NOT allocate ports in the range 0 - 1023 (the Well-
 /// Known Port range) to discourage clients from using TURN to run
 /// standard services.
+///
+/// The server checks if the request contains a REQUESTED-TRANSPORT
+/// attribute.  If the REQUESTED-TRANSPORT attribute is not included or
+/// is malformed, the server rejects the request with a 400 (Bad Request)
+/// error.  Otherwise, if the attribute is included but specifies a
+/// protocol that is not supported by the server, the server rejects the
+/// request with a 442 (Unsupported Transport Protocol) error.
+///
+/// The server computes the lifetime as the minimum of the client's
+/// proposed lifetime and the server's maximum allowed lifetime.  If this
+/// computed value is greater than the default lifetime, then the server
+/// uses the computed lifetime as the initial value of the time-to-expiry
+/// timer.  Otherwise, the server uses the default lifetime.
+///
+/// If the server does not support the address family requested by the
+/// client in the REQUESTED-ADDRESS-FAMILY attribute, it MUST generate an
+/// Allocate error response, and it MUST include an ERROR-CODE attribute
+/// with the 440 (Address Family not Supported) response code.  If the
+/// REQUESTED-ADDRESS-FAMILY attribute is absent, the server MUST allocate
+/// an IPv4-relayed transport address for the TURN client.
+///
+/// The server checks if the request contains both a RESERVATION-TOKEN
+/// and an EVEN-PORT, or a RESERVATION-TOKEN and a REQUESTED-ADDRESS-
+/// FAMILY attribute.  If yes, then the server rejects the request with a
+/// 400 (Bad Request) error.  If the token is not valid, the server
+/// rejects the request with a 508 (Insufficient Capacity) error.
+///
+/// If the client includes an EVEN-PORT attribute, the server allocates
+/// an even port, and if the R bit is set, reserves the next-higher port
+/// and includes a RESERVATION-TOKEN attribute in the response.  If the
+/// server cannot satisfy the request, it rejects the request with a 508
+/// (Insufficient Capacity) error.
+///
+/// If the server is over capacity or draining, the control service can
+/// give an alternate server with the key of the user, the request is
+/// then rejected with a 300 (Try Alternate) error carrying it in an
+/// ALTERNATE-SERVER attribute.
+///
+/// The server should reject new allocations that would exceed the limit
+/// on the allowed number of allocations active at one time for a given
+/// username with a 486 (Allocation Quota Exceeded).
+///
+/// a relay socket is bound to the relayed port for the peers outside
+/// the group, when it cannot be bound the port is released and the
+/// request is rejected with a 508 (Insufficient Capacity) error. the
+/// port also identifies the allocation to the other nodes of the
+/// same group, their data is relayed without the socket.
 #[rustfmt::skip]
 pub async fn process<'a>(ctx: Context, m: MessageReader<'a>, w: &'a mut BytesMut) -> Result<Response<'a>> {
-    let u = match m.get::<UserName>() {
-        Some(u) => u?,
-        _ => return reject(ctx, m, w, Unauthorized).await,
+    let (u, key) = match ctx.auth(&m).await {
+        Err(e) => return reject(ctx, m, w, e).await,
+        Ok(a) => a,
+    };
+
+    if ctx.state.has_allocation(&ctx.addr).await {
+        return match ctx.state.get_transaction(&ctx.addr, m.token).await {
+            Some(b) => replay(ctx, b, w),
+            None => reject(ctx, m, w, AllocationMismatch).await
+        }
+    }
+
+    if let Some(a) = ctx.state.get_alternate(&ctx.addr).await {
+        return redirect(&ctx, &m, &key, a, w)
+    }
+
+    match m.get::<ReqeestedTransport>() {
+        Some(Ok(UDP)) => (),
+        Some(Ok(_)) => return reject(ctx, m, w, UnsupportedTransportAddress).await,
+        _ => return reject(ctx, m, w, BadRequest).await,
+    }
+
+    let f = match m.get::<RequestedAddressFamily>() {
+        Some(Ok(f)) => f,
+        Some(Err(_)) => return reject(ctx, m, w, BadRequest).await,
+        None => FAMILY_IPV4,
     };
 
-    if m.get::<ReqeestedTransport>().is_none() {
-        return reject(ctx, m, w, ServerError).await
+    if f != ctx.family() {
+        return reject(ctx, m, w, AddressFamilyNotSupported).await
     }
 
-    let key = match ctx.state.get_key(&ctx.addr, u).await {
-        None => return reject(ctx, m, w, Unauthorized).await,
-        Some(p) => p,
+    let l = match m.get::<Lifetime>() {
+        Some(Ok(l)) => l.clamp(DEFAULT_LIFETIME, MAX_LIFETIME),
+        Some(Err(_)) => return reject(ctx, m, w, BadRequest).await,
+        None => DEFAULT_LIFETIME,
     };
 
-    let port = match ctx.state.alloc_port(&ctx.addr).await {
-        None => return reject(ctx, m, w, Unauthorized).await,
-        Some(p) => p,
+    let e = match m.get::<EvenPort>() {
+        Some(Ok(r)) => Some(r),
+        Some(Err(_)) => return reject(ctx, m, w, BadRequest).await,
+        None => None,
     };
-    
+
+    let t = match m.get::<ReservationToken>() {
+        Some(Ok(t)) => Some(t),
+        Some(Err(_)) => return reject(ctx, m, w, BadRequest).await,
+        None => None,
+    };
+
+    if t.is_some() && (e.is_some() || m.get::<RequestedAddressFamily>().is_some()) {
+        return reject(ctx, m, w, BadRequest).await
+    }
+
+    let allocated = match (t, e) {
+        (Some(t), _) => ctx.state.redeem_port(&ctx.addr, t).await.map(|p| (p, None)),
+        (None, Some(r)) => ctx.state.alloc_even_port(&ctx.addr, r).await,
+        (None, None) => ctx.state.alloc_port(&ctx.addr).await.map(|p| (p, None)),
+    };
+
+    let (port, token) = match allocated {
+        Err(e) => return reject(ctx, m, w, e).await,
+        Ok(a) => a,
+    };
+
+    let r = match relay::bind(&ctx, port).await {
+        Ok(r) => r,
+        Err(_) => {
+            ctx.state.release_port(&ctx.addr, port).await;
+            return reject(ctx, m, w, InsufficientCapacity).await
+        }
+    };
+
+    ctx.state.set_relay(&ctx.addr, r).await;
     log::info!(
-        "{:?} [{:?}] allocate port={}", 
+        "{:?} [{:?}] allocate port={} timeout={}", 
         &ctx.addr,
         u,
         port,
+        l,
     );
 
-    match m.integrity(&key) {
-        Err(_) => reject(ctx, m, w, Unauthorized).await,
-        Ok(_) => resolve(&ctx, &m, &key, port, w).await,
-    }
+    ctx.state.refresh(&ctx.addr, l).await;
+    resolve(&ctx, &m, &key, port, token, l, w).await
 }
@@ -13,12 +13,35 @@ use super::{
 };
 
 use stun::attribute::{
+    ErrKind::UnknownAttribute,
+    UnknownAttributes,
     XorMappedAddress,
     MappedAddress,
     ResponseOrigin,
-    Software
+    ErrorCode,
+    Software,
+    Error
 };
 
+/// return binding error response
+///
+/// the request contains comprehension-required attributes
+/// that are not understood, they are listed in the
+/// UNKNOWN-ATTRIBUTES attribute of the 420 response.
+#[inline(always)]
+fn reject<'a>(ctx: Context, m: MessageReader, w: &'a mut BytesMut) -> Result<Response<'a>> {
+    let mut pack = MessageWriter::derive(Kind::BindingError, &m, w);
+    pack.append::<ErrorCode>(Error::from(UnknownAttribute));
+    pack.append::<UnknownAttributes>(m.unknown().to_vec());
+    pack.append::<Software>(SOFTWARE);
+    pack.try_into(None)?;
+    if m.fingerprint() {
+        pack.fingerprint();
+    }
+
+    Ok(Some((w, ctx.addr, ctx.transport)))
+}
+
 /// process binding request
 ///
 /// [rfc8489](https://tools.ietf.org/html/rfc8489)
@@ -41,8 +64,16 @@ use stun::attribute::{
 /// attribute within the body of the STUN response will remain untouched.
 /// In this way, the client can learn its reflexive transport address
 /// allocated by the outermost NAT with respect to the STUN server.
+///
+/// Binding requests are not authenticated, so the node also serves
+/// as a plain STUN server for ICE agents, the response carries a
+/// FINGERPRINT when the request does.
 #[rustfmt::skip]
 pub fn process<'a>(ctx: Context, payload: MessageReader, w: &'a mut BytesMut) -> Result<Response<'a>> {
+    if !payload.unknown().is_empty() {
+        return reject(ctx, payload, w)
+    }
+
     log::info!("{:?} request binding", &ctx.addr);
     let mut pack = MessageWriter::derive(Kind::BindingResponse, &payload, w);
     pack.append::<XorMappedAddress>(*ctx.addr.as_ref());
@@ -50,5 +81,9 @@ pub fn process<'a>(ctx: Context, payload: MessageReader, w: &'a mut BytesMut) ->
     pack.append::<ResponseOrigin>(ctx.conf.external);
     pack.append::<Software>(SOFTWARE);
     pack.try_into(None)?;
-    Ok(Some((w, ctx.addr)))
+    if payload.fingerprint() {
+        pack.fingerprint();
+    }
+
+    Ok(Some((w, ctx.addr, ctx.transport)))
 }
@@ -16,30 +16,30 @@ use stun::attribute::{
     Error,
     ErrorCode,
     Realm,
-    UserName,
+    Nonce,
     ChannelNumber,
     XorPeerAddress
 };
 
 use stun::attribute::ErrKind::{
-    BadRequest,
-    Unauthorized,
-    InsufficientCapacity,
+    BadRequest
 };
 
 /// return channel binding error response
 #[inline(always)]
-fn reject<'a>(
+async fn reject<'a>(
     ctx: Context, 
     m: MessageReader<'a>, 
     w: &'a mut BytesMut,
     e: ErrKind, 
 ) -> Result<Response<'a>> {
+    let nonce = ctx.state.get_nonce(&ctx.addr).await;
     let mut pack = MessageWriter::derive(Kind::ChannelBindError, &m, w);
     pack.append::<ErrorCode>(Error::from(e));
     pack.append::<Realm>(&ctx.conf.realm);
+    pack.append::<Nonce>(&nonce);
     pack.try_into(None)?;
-    Ok(Some((w, ctx.addr)))
+    Ok(Some((w, ctx.addr, ctx.transport)))
 }
 
 /// return channel binding ok response
@@ -52,7 +52,7 @@ fn resolve<'a>(
 ) -> Result<Response<'a>> {
     MessageWriter::derive(Kind::ChannelBindResponse, m, w)
         .try_into(Some(p))?;
-    Ok(Some((w, ctx.addr.clone())))
+    Ok(Some((w, ctx.addr.clone(), ctx.transport)))
 }
 
 /// process channel binding request
@@ -76,6 +76,9 @@ fn resolve<'a>(
 /// permission for the IP address in the XOR-PEER-ADDRESS attribute as
 /// described in Section 9.
 ///
+/// the channel to a peer outside the group is bound to its transport
+/// address and relayed through the relay socket of the allocation.
+///
 /// NOTE: A server need not do anything special to implement
 /// idempotency of ChannelBind requests over UDP using the
 /// "stateless stack approach".  Retransmitted ChannelBind requests
@@ -87,36 +90,35 @@ fn resolve<'a>(
 /// retransmission.
 #[rustfmt::skip]
 pub async fn process<'a>(ctx: Context, m: MessageReader<'a>, w: &'a mut BytesMut) -> Result<Response<'a>> {
-    let u = match m.get::<UserName>() {
-        Some(u) => u?,
-        _ => return reject(ctx, m, w, Unauthorized),
+    let (u, key) = match ctx.auth(&m).await {
+        Err(e) => return reject(ctx, m, w, e).await,
+        Ok(a) => a,
     };
 
     let c = match m.get::<ChannelNumber>() {
         Some(c) => c?,
-        _ => return reject(ctx, m, w, BadRequest),
+        _ => return reject(ctx, m, w, BadRequest).await,
     };
     
     let p = match m.get::<XorPeerAddress>() {
-        Some(a) => a?.port(),
-        _ => return reject(ctx, m, w, BadRequest)
+        Some(a) => a?,
+        _ => return reject(ctx, m, w, BadRequest).await
     };
 
     if !(0x4000..=0x4FFF).contains(&c) {
-        return reject(ctx, m, w, BadRequest)
-    }
-
-    let key = match ctx.state.get_key(&ctx.addr, u).await {
-        None => return reject(ctx, m, w, Unauthorized),
-        Some(a) => a,
-    };
-
-    if m.integrity(&key).is_err() {
-        return reject(ctx, m, w, Unauthorized);
+        return reject(ctx, m, w, BadRequest).await
     }
     
-    if ctx.state.bind_channel(&ctx.addr, p, c).await.is_none() {
-        return reject(ctx, m, w, InsufficientCapacity);
+    if ctx.is_group_peer(&p) {
+        if ctx.state.bind_channel(&ctx.addr, p.port(), c).await.is_none() {
+            return reject(ctx, m, w, BadRequest).await;
+        }
+
+        // the channel binding also installs or
+        // refreshes the permission towards the peer.
+        ctx.state.bind_port(&ctx.addr, p.port()).await;
+    } else if ctx.state.bind_peer_channel(&ctx.addr, &p, c).await.is_none() {
+        return reject(ctx, m, w, BadRequest).await;
     }
     
     log::info!(
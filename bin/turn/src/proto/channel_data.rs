@@ -1,5 +1,6 @@
 use stun::ChannelData;
 use super::{
+    relay,
     Context, 
     Response
 };
@@ -32,14 +33,28 @@ use super::{
 /// the Length field in the ChannelData message is 0, then there will be
 /// no data in the UDP datagram, but the UDP datagram is still formed and
 /// sent [(Section 4.1 of [RFC6263])](https://tools.ietf.org/html/rfc6263#section-4.1).
+///
+/// the peer is another node of the same group, so the message is
+/// discarded as well when the permission of either node towards
+/// the other has expired, even if the channel is still bound, or
+/// when the relayed bandwidth of the allocation is exhausted. it
+/// is sent through the transport the peer is connected over.
+/// the channel bound to a peer outside the group is relayed
+/// through the relay socket of the allocation.
 #[rustfmt::skip]
 pub async fn process(ctx: Context, data: ChannelData<'_>) -> Response<'_> {
-    let n = data.number;
-    Some((
-        data.buf,
-        match ctx.state.get_channel_bond(&ctx.addr, n).await {
-            Some(x) => x,
-            None => return None,
-        },
-    ))
+    if let Some(p) = ctx.state.get_peer_channel(&ctx.addr, data.number).await {
+        relay::send(&ctx, &p, &data.buf[4..]).await;
+        return None
+    }
+
+    let a = ctx.state.get_channel_bond(&ctx.addr, data.number).await?;
+    ctx.state.get_bond_port(&a, &ctx.addr).await?;
+    ctx.state.get_bond_port(&ctx.addr, &a).await?;
+    if !ctx.state.is_allowed(&ctx.addr, data.buf.len()).await {
+        return None
+    }
+
+    let t = ctx.state.get_transport(&a).await?;
+    Some((data.buf, a, t))
 }
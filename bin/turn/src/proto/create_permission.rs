@@ -16,29 +16,30 @@ use stun::attribute::{
     ErrorCode,
     Error,
     Realm,
-    UserName,
+    Nonce,
     XorPeerAddress
 };
 
 use stun::attribute::ErrKind::{
     BadRequest,
-    Unauthorized,
     AllocationMismatch,
 };
 
 /// return create permission error response
 #[inline(always)]
-fn reject<'a>(
+async fn reject<'a>(
     ctx: Context, 
     m: MessageReader<'a>, 
     w: &'a mut BytesMut,
     e: ErrKind,
 ) -> Result<Response<'a>> {
+    let nonce = ctx.state.get_nonce(&ctx.addr).await;
     let mut pack = MessageWriter::derive(Kind::CreatePermissionError, &m, w);
     pack.append::<ErrorCode>(Error::from(e));
     pack.append::<Realm>(&ctx.conf.realm);
+    pack.append::<Nonce>(&nonce);
     pack.try_into(None)?;
-    Ok(Some((w, ctx.addr)))
+    Ok(Some((w, ctx.addr, ctx.transport)))
 }
 
 /// return create permission ok response
@@ -51,7 +52,7 @@ fn resolve<'a>(
 ) -> Result<Response<'a>> {
     MessageWriter::derive(Kind::CreatePermissionResponse, m, w)
         .try_into(Some(p))?;
-    Ok(Some((w, ctx.addr.clone())))
+    Ok(Some((w, ctx.addr.clone(), ctx.transport)))
 }
 
 /// process create permission request
@@ -88,33 +89,33 @@ fn resolve<'a>(
 /// The server then responds with a CreatePermission success response.
 /// There are no mandatory attributes in the success response.
 ///
+/// the permission towards a node of the group is installed for its
+/// allocation, the one towards a peer outside the group for its IP
+/// address.
+///
 /// > NOTE: A server need not do anything special to implement
 /// idempotency of CreatePermission requests over UDP using the
 /// "stateless stack approach".  Retransmitted CreatePermission
 /// requests will simply refresh the permissions.
 #[rustfmt::skip]
 pub async fn process<'a>(ctx: Context, m: MessageReader<'a>, w: &'a mut BytesMut) -> Result<Response<'a>> {
-    let u = match m.get::<UserName>() {
-        Some(u) => u?,
-        _ => return reject(ctx, m, w, Unauthorized),
+    let (u, key) = match ctx.auth(&m).await {
+        Err(e) => return reject(ctx, m, w, e).await,
+        Ok(a) => a,
     };
 
     let p = match m.get::<XorPeerAddress>() {
-        Some(a) => a?.port(),
-        _ => return reject(ctx, m, w, BadRequest)
+        Some(a) => a?,
+        _ => return reject(ctx, m, w, BadRequest).await
     };
 
-    let key = match ctx.state.get_key(&ctx.addr, u).await {
-        None => return reject(ctx, m, w, Unauthorized),
-        Some(a) => a,
+    let bound = match ctx.is_group_peer(&p) {
+        true => ctx.state.bind_port(&ctx.addr, p.port()).await,
+        false => ctx.state.bind_peer(&ctx.addr, &p).await,
     };
 
-    if m.integrity(&key).is_err() {
-        return reject(ctx, m, w, Unauthorized);
-    }
-
-    if ctx.state.bind_port(&ctx.addr, p).await.is_none() {
-        return reject(ctx, m, w, AllocationMismatch);
+    if bound.is_none() {
+        return reject(ctx, m, w, AllocationMismatch).await;
     }
 
     log::info!(
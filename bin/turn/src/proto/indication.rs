@@ -6,6 +6,7 @@ use std::{
 };
 
 use super::{
+    relay,
     Context, 
     Response
 };
@@ -65,32 +66,64 @@ use stun::attribute::{
 /// and [15](https://tools.ietf.org/html/rfc8656#section-15).
 /// 
 /// The resulting UDP datagram is then sent to the peer.
+///
+/// the peer is another node of the same group, so the datagram
+/// reaches it as a Data indication whose XOR-PEER-ADDRESS is the
+/// relayed transport address of the sender, it is discarded when
+/// the peer has no permission installed towards the sender either.
+/// it is discarded as well when the relayed bandwidth of the 
+/// allocation is exhausted. the datagram to a peer outside the
+/// group is sent through the relay socket of the allocation.
 #[rustfmt::skip]
 pub async fn process<'a>(ctx: Context, m: MessageReader<'a>, w: &'a mut BytesMut) -> Result<Response<'a>> {
-    let pp = match m.get::<XorPeerAddress>() {
-        Some(x) => x?.port(),
+    if !m.unknown().is_empty() {
+        return Ok(None)
+    }
+
+    let pa = match m.get::<XorPeerAddress>() {
+        Some(Ok(x)) => x,
         _ => return Ok(None),
     };
     
     let d = match m.get::<Data>() {
-        Some(x) => x?,
+        Some(Ok(x)) => x,
         _ => return Ok(None),
     };
 
+    if !ctx.is_group_peer(&pa) {
+        relay::send(&ctx, &pa, d).await;
+        return Ok(None)
+    }
+
+    let pp = pa.port();
+
     let a = match ctx.state.get_port_bond(&ctx.addr, pp).await {
         None => return Ok(None),
         Some(a) => a,
     };
 
+    if ctx.state.get_bond_port(&a, &ctx.addr).await.is_none() {
+        return Ok(None)
+    }
+
+    if !ctx.state.is_allowed(&ctx.addr, d.len()).await {
+        return Ok(None)
+    }
+
     let p = match ctx.state.get_bond_port(&ctx.addr, &a).await {
         None => return Ok(None),
         Some(p) => p,
     };
 
+    let t = match ctx.state.get_transport(&a).await {
+        None => return Ok(None),
+        Some(t) => t,
+    };
+
     let s = Arc::new(SocketAddr::new(ctx.conf.external.ip(), p));
     let mut pack = MessageWriter::derive(Kind::DataIndication, &m, w);
     pack.append::<XorPeerAddress>(*s.as_ref());
     pack.append::<Data>(d);
     pack.try_into(None)?;
-    Ok(Some((w, a)))
+    Ok(Some((w, a, t)))
 }
@@ -5,13 +5,20 @@ mod channel_data;
 mod create_permission;
 mod indication;
 mod refresh;
+mod relay;
 
 use anyhow::Result;
 use bytes::BytesMut;
 use super::{
     argv::Argv,
-    state::State,
-    server::ThreadLocal
+    server::{
+        ThreadLocal,
+        Router
+    },
+    state::{
+        State,
+        Transport
+    }
 };
 
 use std::{
@@ -26,6 +33,22 @@ use stun::{
     MessageReader as Message,
 };
 
+use stun::attribute::{
+    FAMILY_IPV4,
+    FAMILY_IPV6,
+    ErrKind,
+    MessageIntegrity,
+    UserName,
+    Realm,
+    Nonce
+};
+
+use stun::attribute::ErrKind::{
+    BadRequest,
+    Unauthorized,
+    StaleNonce
+};
+
 #[rustfmt::skip]
 static SOFTWARE: &str = concat!(
     env!("CARGO_PKG_NAME"), 
@@ -33,10 +56,17 @@ static SOFTWARE: &str = concat!(
     env!("CARGO_PKG_VERSION")
 );
 
+/// the default lifetime of an allocation in seconds.
+pub(crate) const DEFAULT_LIFETIME: u32 = 600;
+
+/// the maximum lifetime of an allocation in seconds.
+pub(crate) const MAX_LIFETIME: u32 = 3600;
+
 #[rustfmt::skip]
 pub(crate) type Response<'a> = Option<(
     &'a [u8],
-    Arc<SocketAddr>
+    Arc<SocketAddr>,
+    Transport
 )>;
 
 /// message context
@@ -44,6 +74,91 @@ pub struct Context {
     pub conf: Arc<Argv>,
     pub state: Arc<State>,
     pub addr: Arc<SocketAddr>,
+    pub transport: Transport,
+    pub router: Arc<Router>,
+}
+
+impl Context {
+    /// the address family of the relayed transport addresses.
+    ///
+    /// the relayed transport addresses share the external
+    /// address of the node, so only its family is supported,
+    /// an IPv6 relayed address requires an IPv6 external
+    /// address, and the default IPv4 one an IPv4 external
+    /// address.
+    pub fn family(&self) -> u8 {
+        match self.conf.external.is_ipv4() {
+            true => FAMILY_IPV4,
+            false => FAMILY_IPV6,
+        }
+    }
+
+    /// whether the peer is a node of the group.
+    ///
+    /// the relayed transport addresses of the group share the
+    /// external address, their peers are reached by the port
+    /// of the allocation, the other peers through the relay
+    /// socket of the allocation.
+    pub fn is_group_peer(&self, p: &SocketAddr) -> bool {
+        p.ip() == self.conf.external.ip()
+    }
+
+    /// long-term credential mechanism
+    ///
+    /// [rfc8489](https://tools.ietf.org/html/rfc8489#section-9.2.4)
+    ///
+    /// If the message does not contain a MESSAGE-INTEGRITY attribute, the
+    /// server MUST generate an error response with an error code of 401
+    /// (Unauthenticated).  This response MUST include a REALM value and a
+    /// NONCE.
+    ///
+    /// If the message contains a MESSAGE-INTEGRITY attribute, but is
+    /// missing the USERNAME, REALM or NONCE attribute, the server MUST
+    /// generate an error response with an error code of 400 (Bad Request).
+    ///
+    /// If the NONCE is no longer valid, the server MUST generate an error
+    /// response with an error code of 438 (Stale Nonce).  This response
+    /// MUST include NONCE and REALM attributes.
+    ///
+    /// If the value of the USERNAME attribute is not valid, or the value
+    /// of MESSAGE-INTEGRITY is incorrect, the server MUST generate an
+    /// error response with an error code of 401 (Unauthenticated).
+    ///
+    /// the key is requested from the control service through the
+    /// broker the first time a node is seen.
+    #[rustfmt::skip]
+    pub async fn auth<'a>(&self, m: &Message<'a>) -> std::result::Result<(&'a str, Arc<[u8; 16]>), ErrKind> {
+        if m.get::<MessageIntegrity>().is_none() {
+            return Err(Unauthorized)
+        }
+
+        let (u, r, n) = match (
+            m.get::<UserName>(),
+            m.get::<Realm>(),
+            m.get::<Nonce>()
+        ) {
+            (Some(Ok(u)), Some(Ok(r)), Some(Ok(n))) => (u, r, n),
+            _ => return Err(BadRequest)
+        };
+
+        if r != self.conf.realm {
+            return Err(Unauthorized)
+        }
+
+        if n != self.state.get_nonce(&self.addr).await.as_str() {
+            return Err(StaleNonce)
+        }
+
+        let key = match self.state.get_key(&self.addr, u, self.transport).await {
+            None => return Err(Unauthorized),
+            Some(k) => k,
+        };
+
+        match m.integrity(&key) {
+            Err(_) => Err(Unauthorized),
+            Ok(_) => Ok((u, key)),
+        }
+    }
 }
 
 /// process udp message 
@@ -60,7 +175,8 @@ impl Proto {
     /// process udp data
     ///
     /// receive STUN encoded Bytes, 
-    /// and return any Bytes that can be responded to and the target address.
+    /// and return any Bytes that can be responded to, the target address
+    /// and the transport of the target.
     /// Note: unknown message is not process.
     /// 
     /// In a typical configuration, a TURN client is connected to a private
@@ -166,8 +282,14 @@ impl Proto {
     /// The client may have multiple allocations on a server at the same
     /// time.
     #[rustfmt::skip]
-    pub async fn handler<'a>(&self, b: &'a [u8], w: &'a mut BytesMut, a: SocketAddr) -> Result<Response<'a>> {
-        let ctx = self.get_context(a);
+    pub async fn handler<'a>(
+        &self,
+        b: &'a [u8],
+        w: &'a mut BytesMut,
+        a: SocketAddr,
+        t: Transport
+    ) -> Result<Response<'a>> {
+        let ctx = self.get_context(a, t);
         Ok(match Payload::try_from(b)? {
             Payload::ChannelData(x) => channel_data::process(ctx, x).await,
             Payload::Message(x) => Self::message_process(ctx, x, w).await?,
@@ -311,11 +433,13 @@ impl Proto {
     }
     
     /// builder of message context from thread local.
-    fn get_context(&self, a: SocketAddr) -> Context {
+    fn get_context(&self, a: SocketAddr, t: Transport) -> Context {
         Context {
             state: self.local.state.clone(),
             conf: self.local.conf.clone(),
+            router: self.local.router.clone(),
             addr: Arc::new(a),
+            transport: t,
         }
     }
 }
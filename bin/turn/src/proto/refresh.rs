@@ -2,7 +2,9 @@ use bytes::BytesMut;
 use anyhow::Result;
 use super::{
     Context, 
-    Response
+    Response,
+    DEFAULT_LIFETIME,
+    MAX_LIFETIME
 };
 
 use stun::{
@@ -12,26 +14,36 @@ use stun::{
 };
 
 use stun::attribute::{
-    ErrKind::Unauthorized,
     ErrKind,
     Error,
     ErrorCode,
     Lifetime,
-    UserName
+    Realm,
+    Nonce,
+    RequestedAddressFamily
+};
+
+use stun::attribute::ErrKind::{
+    PeerAddressFamilyMismatch,
+    BadRequest,
+    AllocationMismatch
 };
 
 /// return refresh error response
 #[inline(always)]
-fn reject<'a>(
+async fn reject<'a>(
     ctx: Context, 
     m: MessageReader<'a>, 
     w: &'a mut BytesMut, 
     e: ErrKind
 ) -> Result<Response<'a>> {
+    let nonce = ctx.state.get_nonce(&ctx.addr).await;
     let mut pack = MessageWriter::derive(Kind::RefreshError, &m, w);
     pack.append::<ErrorCode>(Error::from(e));
+    pack.append::<Realm>(&ctx.conf.realm);
+    pack.append::<Nonce>(&nonce);
     pack.try_into(None)?;
-    Ok(Some((w, ctx.addr)))
+    Ok(Some((w, ctx.addr, ctx.transport)))
 }
 
 /// return refresh ok response
@@ -46,7 +58,7 @@ pub fn resolve<'a>(
     let mut pack = MessageWriter::derive(Kind::RefreshResponse, m , w);
     pack.append::<Lifetime>(lifetime);
     pack.try_into(Some(p))?;
-    Ok(Some((w, ctx.addr.clone())))
+    Ok(Some((w, ctx.addr.clone(), ctx.transport)))
 }
 
 /// process refresh request
@@ -90,24 +102,23 @@ pub fn resolve<'a>(
 /// this as equivalent to a success response (see below).
 #[rustfmt::skip]
 pub async fn process<'a>(ctx: Context, m: MessageReader<'a>, w: &'a mut BytesMut) -> Result<Response<'a>> {
-    let u = match m.get::<UserName>() {
-        Some(u) => u?,
-        _ => return reject(ctx, m, w, Unauthorized),
+    let (u, key) = match ctx.auth(&m).await {
+        Err(e) => return reject(ctx, m, w, e).await,
+        Ok(a) => a,
     };
 
-    let l = match m.get::<Lifetime>() {
-        Some(l) => l?,
-        _ => 600,
-    };
+    match m.get::<RequestedAddressFamily>() {
+        Some(Ok(f)) if f != ctx.family() => return reject(ctx, m, w, PeerAddressFamilyMismatch).await,
+        Some(Err(_)) => return reject(ctx, m, w, BadRequest).await,
+        _ => (),
+    }
 
-    let key = match ctx.state.get_key(&ctx.addr, u).await {
-        None => return reject(ctx, m, w, Unauthorized),
-        Some(a) => a,
+    let l = match m.get::<Lifetime>() {
+        Some(Ok(0)) => 0,
+        Some(Ok(l)) => l.clamp(DEFAULT_LIFETIME, MAX_LIFETIME),
+        Some(Err(_)) => return reject(ctx, m, w, BadRequest).await,
+        None => DEFAULT_LIFETIME,
     };
-
-    if m.integrity(&key).is_err() {
-        return reject(ctx, m, w, Unauthorized);
-    }
     
     log::info!(
         "{:?} [{:?}] refresh timeout={}", 
@@ -116,6 +127,9 @@ pub async fn process<'a>(ctx: Context, m: MessageReader<'a>, w: &'a mut BytesMut
         l,
     );
 
-    ctx.state.refresh(&ctx.addr, l).await;
+    if ctx.state.refresh(&ctx.addr, l).await.is_none() {
+        return reject(ctx, m, w, AllocationMismatch).await
+    }
+
     resolve(&ctx, &m, l, &key, w)
 }
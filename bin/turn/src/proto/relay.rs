@@ -0,0 +1,168 @@
+use anyhow::Result;
+use tokio::net::UdpSocket;
+use super::Context;
+use bytes::{
+    BufMut,
+    BytesMut
+};
+
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    io
+};
+
+use crate::{
+    server::Router,
+    state::{
+        State,
+        Relay
+    }
+};
+
+use stun::{
+    Kind,
+    MessageWriter
+};
+
+use stun::attribute::{
+    XorPeerAddress,
+    Data
+};
+
+/// encode a ChannelData message.
+///
+/// ```no_run
+/// let mut w = BytesMut::new();
+/// let b = channel_data(0x4000, &[0xff], &mut w);
+/// assert_eq!(b, &[0x40, 0x00, 0x00, 0x01, 0xff]);
+/// ```
+fn channel_data<'a>(c: u16, data: &[u8], w: &'a mut BytesMut) -> &'a [u8] {
+    w.clear();
+    w.put_u16(c);
+    w.put_u16(data.len() as u16);
+    w.extend_from_slice(data);
+    w
+}
+
+/// encode a Data indication.
+///
+/// ```no_run
+/// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+/// let mut w = BytesMut::new();
+/// assert!(data_indication(peer, &[0xff], &mut w).is_ok());
+/// ```
+fn data_indication<'a>(p: SocketAddr, data: &[u8], w: &'a mut BytesMut) -> Result<&'a [u8]> {
+    let token = rand::random::<[u8; 12]>();
+    let mut pack = MessageWriter::new(Kind::DataIndication, &token, w);
+    pack.append::<XorPeerAddress>(p);
+    pack.append::<Data>(data);
+    pack.try_into(None)?;
+    Ok(w)
+}
+
+/// relay the datagrams received on the relay socket.
+///
+/// When the server receives a UDP datagram at a currently allocated
+/// relayed transport address, the server looks up the allocation
+/// associated with the relayed transport address.  The server then
+/// checks to see whether the set of permissions for the allocation
+/// allow the relaying of the UDP datagram.
+///
+/// If relaying is permitted, then the server checks if there is a
+/// channel bound to the peer that sent the UDP datagram.  If a channel
+/// is bound, then processing proceeds as described in Section 12.5, a
+/// ChannelData message is sent to the client.  If no channel is
+/// bound to the peer, then the server forms and sends a Data
+/// indication.
+///
+/// the datagrams are discarded as well when the relayed bandwidth
+/// of the allocation is exhausted.
+#[rustfmt::skip]
+async fn forward(
+    socket: Arc<UdpSocket>,
+    a: Arc<SocketAddr>,
+    state: Arc<State>,
+    router: Arc<Router>,
+    size: usize
+) {
+    let mut buf = vec![0u8; size];
+    let mut w = BytesMut::with_capacity(size);
+    loop {
+        let (s, p) = match socket.recv_from(&mut buf).await {
+            Ok(r) => r,
+            Err(_) => continue
+        };
+
+        if !state.has_peer(&a, &p).await || !state.is_allowed(&a, s).await {
+            continue;
+        }
+
+        let t = match state.get_transport(&a).await {
+            Some(t) => t,
+            None => break
+        };
+
+        let b = match state.get_peer_channel_number(&a, &p).await {
+            Some(c) => channel_data(c, &buf[..s], &mut w),
+            None => match data_indication(p, &buf[..s], &mut w) {
+                Ok(b) => b,
+                Err(_) => continue
+            }
+        };
+
+        if let Err(e) = router.send(b, t, &a).await {
+            log::error!("relay io error: {}", e);
+        }
+    }
+}
+
+/// send data to a peer outside the group.
+///
+/// the data is sent through the relay socket of the allocation,
+/// it is discarded when no permission is installed for the peer,
+/// or when the relayed bandwidth of the allocation is exhausted.
+///
+/// ```no_run
+/// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+/// // send(&ctx, &peer, &[0xff]).await
+/// ```
+pub async fn send(ctx: &Context, p: &SocketAddr, data: &[u8]) -> Option<()> {
+    if !ctx.state.has_peer(&ctx.addr, p).await {
+        return None
+    }
+
+    if !ctx.state.is_allowed(&ctx.addr, data.len()).await {
+        return None
+    }
+
+    let socket = ctx.state.get_relay(&ctx.addr).await?;
+    socket.send_to(data, p).await.ok()?;
+    Some(())
+}
+
+/// bind the relay socket of an allocation.
+///
+/// the socket is bound to the relayed port on the listen
+/// address, so that the peers outside the group can reach
+/// the relayed transport address, the datagrams received
+/// on it are relayed to the client until the allocation
+/// is removed.
+///
+/// ```no_run
+/// // let relay = bind(&ctx, 49152).await?;
+/// // ctx.state.set_relay(&ctx.addr, relay).await;
+/// ```
+pub async fn bind(ctx: &Context, port: u16) -> io::Result<Relay> {
+    let addr = SocketAddr::new(ctx.conf.listen.ip(), port);
+    let socket = Arc::new(UdpSocket::bind(addr).await?);
+    let task = tokio::spawn(forward(
+        socket.clone(),
+        ctx.addr.clone(),
+        ctx.state.clone(),
+        ctx.router.clone(),
+        ctx.conf.buffer
+    ));
+
+    Ok(Relay::new(socket, task))
+}
@@ -0,0 +1,281 @@
+use bytes::BytesMut;
+use super::ThreadLocal;
+
+use crate::{
+    proto::{
+        Proto,
+        MAX_LIFETIME
+    },
+    argv::Argv,
+    state::Transport
+};
+
+use anyhow::{
+    Result,
+    bail
+};
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque
+    },
+    io::{
+        self,
+        Read,
+        Write
+    },
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+    time::Duration
+};
+
+use tokio::{
+    net::UdpSocket,
+    time::timeout,
+    sync::mpsc::{
+        channel,
+        Receiver,
+        Sender
+    }
+};
+
+use openssl::ssl::{
+    ErrorCode,
+    Ssl,
+    SslContext,
+    SslFiletype,
+    SslMethod,
+    SslOptions,
+    SslStream
+};
+
+/// the size of the incoming and outgoing queues of each session.
+const QUEUE_SIZE: usize = 256;
+
+/// the path MTU assumed for the DTLS records.
+const MTU: u32 = 1200;
+
+/// the DTLS content type of a handshake record.
+const HANDSHAKE: u8 = 22;
+
+/// the datagrams of a DTLS session.
+///
+/// the records are not read from the socket by openssl, the
+/// datagrams received from the client are queued here, and
+/// each record written by openssl is one outgoing datagram.
+#[derive(Default)]
+struct Datagrams {
+    incoming: VecDeque<Vec<u8>>,
+    outgoing: Vec<Vec<u8>>,
+}
+
+impl Read for Datagrams {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let datagram = match self.incoming.pop_front() {
+            None => return Err(io::ErrorKind::WouldBlock.into()),
+            Some(d) => d
+        };
+
+        let size = std::cmp::min(buf.len(), datagram.len());
+        buf[..size].copy_from_slice(&datagram[..size]);
+        Ok(size)
+    }
+}
+
+impl Write for Datagrams {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.outgoing.push(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// load the certificate chain and the private key.
+#[rustfmt::skip]
+fn load_context(cert: &Path, key: &Path) -> Result<SslContext> {
+    let mut builder = SslContext::builder(SslMethod::dtls())?;
+    builder.set_options(SslOptions::NO_QUERY_MTU);
+    builder.set_certificate_chain_file(cert)?;
+    builder.set_private_key_file(key, SslFiletype::PEM)?;
+    builder.check_private_key()?;
+    Ok(builder.build())
+}
+
+/// send the records written by openssl to the client.
+async fn flush(stream: &mut SslStream<Datagrams>, socket: &UdpSocket, addr: &SocketAddr) {
+    for datagram in stream.get_mut().outgoing.drain(..) {
+        if let Err(e) = socket.send_to(&datagram, addr).await {
+            log::error!("dtls io error: {}", e);
+        }
+    }
+}
+
+/// process a DTLS session.
+///
+/// the handshake is driven by the datagrams of the client,
+/// once it is completed the client is registered to the router
+/// and the records are handed to the proto. the session ends
+/// when it is closed by the client, or when it is idle longer
+/// than an allocation can live, and then the node is removed.
+#[rustfmt::skip]
+async fn session(
+    mut inbound: Receiver<Vec<u8>>,
+    socket: Arc<UdpSocket>,
+    ssl: Ssl,
+    addr: SocketAddr,
+    local: ThreadLocal
+) {
+    let mut stream = match SslStream::new(ssl, Datagrams::default()) {
+        Ok(s) => s,
+        Err(_) => return
+    };
+
+    let idle = Duration::from_secs(MAX_LIFETIME as u64);
+    let (tx, mut rx) = channel::<Vec<u8>>(QUEUE_SIZE);
+    let mut registered = false;
+
+    let state = local.state.clone();
+    let router = local.router.clone();
+    let mut w = BytesMut::with_capacity(local.conf.buffer);
+    let mut buf = vec![0u8; local.conf.buffer];
+    let proto = Proto::builder(local);
+    'session: loop {
+        tokio::select! {
+            datagram = timeout(idle, inbound.recv()) => match datagram {
+                Ok(Some(d)) => stream.get_mut().incoming.push_back(d),
+                _ => break
+            },
+            Some(b) = rx.recv() => {
+                if stream.ssl_write(&b).is_err() {
+                    break
+                }
+            }
+        }
+
+        if !registered {
+            match stream.accept() {
+                Ok(_) => router.register(Transport::Dtls, addr, tx.clone()).await,
+                Err(e) if e.code() == ErrorCode::WANT_READ => {
+                    flush(&mut stream, &socket, &addr).await;
+                    continue
+                },
+                Err(_) => break
+            }
+
+            registered = true;
+        }
+
+        loop {
+            let size = match stream.ssl_read(&mut buf) {
+                Ok(s) if s >= 4 => s,
+                Ok(_) => continue,
+                Err(e) if e.code() == ErrorCode::WANT_READ => break,
+                Err(_) => break 'session
+            };
+
+            if let Ok(Some((b, p, t))) = proto.handler(&buf[..size], &mut w, addr, Transport::Dtls).await {
+                if let Err(e) = router.send(b, t, p.as_ref()).await {
+                    log::error!("dtls io error: {}", e);
+                }
+            }
+        }
+
+        flush(&mut stream, &socket, &addr).await;
+    }
+
+    flush(&mut stream, &socket, &addr).await;
+    if registered {
+        router.remove(Transport::Dtls, &addr).await;
+        state.remove(&Arc::new(addr)).await;
+    }
+}
+
+/// start dtls server.
+///
+/// the DTLS listener shares the allocation engine with the
+/// UDP one, for the networks where the plain STUN messages
+/// are blocked by DPI. it is only started when the listen
+/// address is given, and then requires the certificate chain
+/// and the private key. the datagrams are dispatched to the
+/// session of their source address, a new session is only
+/// started by a handshake record, the ended sessions are
+/// dropped when a new one is started.
+///
+/// # Example
+///
+/// ```no_run
+/// let c = argv::Argv::generate()?;
+/// let t = broker::Broker::new(&c).await?;
+/// let s = state::State::new(t);
+///
+/// // run(c, thread_local).await?
+/// ```
+#[rustfmt::skip]
+pub async fn run(f: Arc<Argv>, local: ThreadLocal) -> Result<()> {
+    let listen = match f.dtls_listen {
+        Some(a) => a,
+        None => return Ok(())
+    };
+
+    let (cert, key) = match (&f.tls_cert, &f.tls_key) {
+        (Some(c), Some(k)) => (c, k),
+        _ => bail!("dtls listener requires tls cert and tls key!")
+    };
+
+    let context = load_context(cert, key)?;
+    let socket = Arc::new(UdpSocket::bind(listen).await?);
+    let mut sessions = HashMap::<SocketAddr, Sender<Vec<u8>>>::with_capacity(1024);
+    let mut buf = vec![0u8; f.buffer];
+    tokio::spawn(async move {
+        loop {
+            let (size, addr) = match socket.recv_from(&mut buf).await {
+                Ok(r) if r.0 > 0 => r,
+                _ => continue
+            };
+
+            if let Some(tx) = sessions.get(&addr) {
+                if !tx.is_closed() {
+                    let _ = tx.try_send(buf[..size].to_vec());
+                    continue;
+                }
+            }
+
+            if buf[0] != HANDSHAKE {
+                continue;
+            }
+
+            let mut ssl = match Ssl::new(&context) {
+                Ok(s) => s,
+                Err(_) => continue
+            };
+
+            if ssl.set_mtu(MTU).is_err() {
+                continue;
+            }
+
+            let (tx, rx) = channel::<Vec<u8>>(QUEUE_SIZE);
+            let _ = tx.try_send(buf[..size].to_vec());
+            sessions.retain(|_, tx| !tx.is_closed());
+            sessions.insert(addr, tx);
+            tokio::spawn(session(
+                rx,
+                socket.clone(),
+                ssl,
+                addr,
+                local.clone()
+            ));
+        }
+    });
+
+    log::info!(
+        "dtls bind to {}",
+        listen
+    );
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
 mod thread;
+mod router;
+mod tls;
+mod dtls;
 
 use tokio::net::UdpSocket;
 use anyhow::Result;
@@ -8,6 +11,7 @@ use super::{
     state::State
 };
 
+pub use router::Router;
 pub use thread::{
     Thread,
     ThreadLocal
@@ -28,6 +32,8 @@ fn get_threads(threads: Option<usize>) -> usize {
 ///
 /// create a specified number of threads, 
 /// each thread processes udp data separately.
+/// the TLS and DTLS servers are started as well when configured,
+/// the responses go through the transport of the client.
 ///
 /// # Example
 ///
@@ -42,11 +48,15 @@ fn get_threads(threads: Option<usize>) -> usize {
 pub async fn run(f: Arc<Argv>, c: Arc<State>) -> Result<()> {
     let s = Arc::new(UdpSocket::bind(f.listen).await?);
     let threads = get_threads(f.threads);
+    let router = Router::new(&s);
     let tl = ThreadLocal {
         state: c.clone(),
         conf: f.clone(),
+        router: router.clone(),
     };
     
+    tls::run(f.clone(), tl.clone()).await?;
+    dtls::run(f.clone(), tl.clone()).await?;
     for _ in 0..threads {
         let mut cx = Thread::builder(tl.clone(), &s);
         tokio::spawn(async move {
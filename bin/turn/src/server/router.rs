@@ -0,0 +1,95 @@
+use crate::state::Transport;
+use stun::util::pad_size;
+use tokio::net::UdpSocket;
+use tokio::sync::{
+    mpsc::Sender,
+    RwLock
+};
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::Arc,
+    io
+};
+
+/// the queues of the client streams.
+type Streams = HashMap<(Transport, SocketAddr), Sender<Vec<u8>>>;
+
+/// response router.
+///
+/// the responses and the relayed data are sent through the
+/// transport the target client arrived on, the clients connected
+/// over TLS or DTLS are registered here with the queue of their
+/// session, the UDP clients are reached through the UDP socket
+/// without looking up the streams.
+pub struct Router {
+    socket: Arc<UdpSocket>,
+    streams: RwLock<Streams>,
+}
+
+impl Router {
+    pub fn new(socket: &Arc<UdpSocket>) -> Arc<Self> {
+        Arc::new(Self {
+            streams: RwLock::new(HashMap::with_capacity(1024)),
+            socket: socket.clone(),
+        })
+    }
+
+    /// register the stream of a client.
+    ///
+    /// ```no_run
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let socket = Arc::new(UdpSocket::bind("127.0.0.1:3478").await?);
+    /// let router = Router::new(&socket);
+    /// let (tx, rx) = tokio::sync::mpsc::channel(256);
+    /// router.register(Transport::Tls, addr, tx).await;
+    /// ```
+    pub async fn register(&self, t: Transport, a: SocketAddr, tx: Sender<Vec<u8>>) {
+        self.streams.write().await.insert((t, a), tx);
+    }
+
+    /// remove the stream of a client.
+    ///
+    /// ```no_run
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let socket = Arc::new(UdpSocket::bind("127.0.0.1:3478").await?);
+    /// let router = Router::new(&socket);
+    /// router.remove(Transport::Tls, &addr).await;
+    /// ```
+    pub async fn remove(&self, t: Transport, a: &SocketAddr) {
+        self.streams.write().await.remove(&(t, *a));
+    }
+
+    /// send data to a client.
+    ///
+    /// over TLS, a ChannelData message is padded to a multiple
+    /// of 4 bytes, DTLS keeps the datagram boundaries so it is
+    /// sent as is. the data is dropped when the queue of the
+    /// session is full, as it would be over UDP.
+    ///
+    /// ```no_run
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let socket = Arc::new(UdpSocket::bind("127.0.0.1:3478").await?);
+    /// let router = Router::new(&socket);
+    /// router.send(&[0x40, 0x00, 0x00, 0x01, 0xff], Transport::Udp, &addr).await?;
+    /// ```
+    #[rustfmt::skip]
+    pub async fn send(&self, b: &[u8], t: Transport, a: &SocketAddr) -> io::Result<()> {
+        if t == Transport::Udp {
+            self.socket.send_to(b, a).await?;
+            return Ok(())
+        }
+
+        if let Some(tx) = self.streams.read().await.get(&(t, *a)) {
+            let mut buf = b.to_vec();
+            if t == Transport::Tls && buf[0] >> 4 == 4 {
+                buf.resize(b.len() + pad_size(b.len()), 0);
+            }
+
+            let _ = tx.try_send(buf);
+        }
+
+        Ok(())
+    }
+}
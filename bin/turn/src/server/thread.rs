@@ -5,21 +5,27 @@ use std::{
     sync::Arc
 };
 
+use super::Router;
 use crate::{
     proto::Proto,
     argv::Argv,
-    state::State
+    state::{
+        State,
+        Transport
+    }
 };
 
 /// thread local context.
 pub struct ThreadLocal {
     pub state: Arc<State>,
     pub conf: Arc<Argv>,
+    pub router: Arc<Router>,
 }
 
 /// server thread worker.
 pub struct Thread {
     socket: Arc<UdpSocket>,
+    router: Arc<Router>,
     writer: BytesMut,
     reader: Vec<u8>,
     proto: Proto,
@@ -31,6 +37,7 @@ impl Thread {
         Self {
             writer: BytesMut::with_capacity(local.conf.buffer),
             reader: vec![0u8; local.conf.buffer],
+            router: local.router.clone(),
             proto: Proto::builder(local),
             socket: socket.clone(),
         }
@@ -40,7 +47,7 @@ impl Thread {
     /// 
     /// read the data packet from the UDP socket and hand 
     /// it to the proto for processing, and send the processed 
-    /// data packet to the specified address.
+    /// data packet to the specified address through the router.
     ///
     /// # Example
     ///
@@ -49,14 +56,16 @@ impl Thread {
     /// let t = broker::Broker::new(&c).await?;
     /// let s = state::State::new(t);
     /// 
+    /// let u = Arc::new(UdpSocket::bind(c.listen).await?);
+    /// let r = Router::new(&u);
     /// let thread_local = ThreadLocal {
     ///     state: s,
-    ///     conf: c
+    ///     conf: c,
+    ///     router: r.clone()
     /// };
     ///
-    /// let s = Arc::new(UdpSocket::bind(c.listen).await?);
     /// tokio::spawn(async move {
-    ///     let mut tr = Thread::builder(thread_local, &s);
+    ///     let mut tr = Thread::builder(thread_local, &u);
     ///     loop { tr.poll().await.unwrap() }
     /// });
     /// ```
@@ -67,16 +76,17 @@ impl Thread {
             None => return
         };
 
-        let (b, p) = match self.proto.handler(
+        let (b, p, t) = match self.proto.handler(
             &self.reader[..s], 
             &mut self.writer, 
-            a
+            a,
+            Transport::Udp
         ).await {
             Ok(Some(x)) => x,
             _ => return
         };
 
-        if let Err(e) = self.socket.send_to(b, p.as_ref()).await {
+        if let Err(e) = self.router.send(b, t, p.as_ref()).await {
             log::error!("udp io error: {}", e);
             std::process::abort();
         }
@@ -97,13 +107,15 @@ impl Thread {
     /// let t = broker::Broker::new(&c).await?;
     /// let s = state::State::new(t);
     /// 
+    /// let u = Arc::new(UdpSocket::bind(c.listen).await?);
+    /// let r = Router::new(&u);
     /// let thread_local = ThreadLocal {
     ///     state: s,
-    ///     conf: c
+    ///     conf: c,
+    ///     router: r.clone()
     /// };
     ///
-    /// let s = Arc::new(UdpSocket::bind(c.listen).await?);
-    /// let mut tr = Thread::builder(thread_local, &s);
+    /// let mut tr = Thread::builder(thread_local, &u);
     /// // tr.read().await
     /// ```
     async fn read(&mut self) -> Option<(usize, SocketAddr)> {
@@ -118,7 +130,8 @@ impl Clone for ThreadLocal {
     fn clone(&self) -> Self {
         Self {
             state: self.state.clone(),
-            conf: self.conf.clone()
+            conf: self.conf.clone(),
+            router: self.router.clone()
         }
     }
 }
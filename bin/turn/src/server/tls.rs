@@ -0,0 +1,187 @@
+use bytes::BytesMut;
+use stun::util::pad_size;
+use super::ThreadLocal;
+
+use crate::{
+    proto::Proto,
+    argv::Argv,
+    state::Transport
+};
+
+use anyhow::{
+    Result,
+    anyhow,
+    bail,
+    ensure
+};
+
+use std::{
+    io::BufReader,
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+    fs::File
+};
+
+use tokio::{
+    sync::mpsc::channel,
+    net::{
+        TcpListener,
+        TcpStream
+    },
+    io::{
+        split,
+        AsyncRead,
+        AsyncReadExt,
+        AsyncWriteExt
+    }
+};
+
+use tokio_rustls::{
+    server::TlsStream,
+    TlsAcceptor
+};
+
+use tokio_rustls::rustls::{
+    internal::pemfile,
+    NoClientAuth,
+    ServerConfig
+};
+
+/// the size of the outgoing queue of each stream.
+const QUEUE_SIZE: usize = 256;
+
+/// load the certificate chain and the private key.
+///
+/// the private key is read as PKCS8 first, then as RSA.
+#[rustfmt::skip]
+fn load_config(cert: &Path, key: &Path) -> Result<ServerConfig> {
+    let certs = pemfile::certs(&mut BufReader::new(File::open(cert)?))
+        .map_err(|_| anyhow!("invalid tls certificate!"))?;
+    let mut keys = pemfile::pkcs8_private_keys(&mut BufReader::new(File::open(key)?))
+        .map_err(|_| anyhow!("invalid tls private key!"))?;
+    if keys.is_empty() {
+        keys = pemfile::rsa_private_keys(&mut BufReader::new(File::open(key)?))
+            .map_err(|_| anyhow!("invalid tls private key!"))?;
+    }
+
+    let key = keys.pop().ok_or_else(|| anyhow!("invalid tls private key!"))?;
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config.set_single_cert(certs, key)?;
+    Ok(config)
+}
+
+/// read a message from the stream.
+///
+/// over TCP and TLS, the messages are delimited by their
+/// own length, the ChannelData message is padded to a
+/// multiple of 4 bytes, the first two bits of a STUN
+/// message are zero.
+#[rustfmt::skip]
+async fn read_message<R: AsyncRead + Unpin>(r: &mut R, buf: &mut [u8]) -> Result<usize> {
+    r.read_exact(&mut buf[..4]).await?;
+    let len = u16::from_be_bytes([buf[2], buf[3]]) as usize;
+    let size = match buf[0] >> 4 {
+        4 => 4 + len + pad_size(len),
+        0..=3 => 20 + len,
+        _ => bail!("invalid message!")
+    };
+
+    ensure!(size <= buf.len(), "message too large!");
+    r.read_exact(&mut buf[4..size]).await?;
+    Ok(size)
+}
+
+/// process a TLS connection.
+///
+/// the client is registered to the router for the lifetime of
+/// the connection, a TURN allocation over TLS ends with its
+/// connection, so the node is removed when it is closed.
+#[rustfmt::skip]
+async fn session(
+    stream: TlsStream<TcpStream>,
+    addr: SocketAddr,
+    local: ThreadLocal
+) {
+    let (mut reader, mut writer) = split(stream);
+    let (tx, mut rx) = channel::<Vec<u8>>(QUEUE_SIZE);
+    let router = local.router.clone();
+    router.register(Transport::Tls, addr, tx).await;
+    tokio::spawn(async move {
+        while let Some(buf) = rx.recv().await {
+            if writer.write_all(&buf).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let state = local.state.clone();
+    let mut w = BytesMut::with_capacity(local.conf.buffer);
+    let mut buf = vec![0u8; local.conf.buffer];
+    let proto = Proto::builder(local);
+    while let Ok(size) = read_message(&mut reader, &mut buf).await {
+        if let Ok(Some((b, p, t))) = proto.handler(&buf[..size], &mut w, addr, Transport::Tls).await {
+            if let Err(e) = router.send(b, t, p.as_ref()).await {
+                log::error!("tls io error: {}", e);
+            }
+        }
+    }
+
+    router.remove(Transport::Tls, &addr).await;
+    state.remove(&Arc::new(addr)).await;
+}
+
+/// start tls server.
+///
+/// the TLS (turns:) listener shares the allocation engine
+/// with the UDP one, it is only started when the listen
+/// address is given, and then requires the certificate
+/// chain and the private key.
+///
+/// # Example
+///
+/// ```no_run
+/// let c = argv::Argv::generate()?;
+/// let t = broker::Broker::new(&c).await?;
+/// let s = state::State::new(t);
+///
+/// // run(c, thread_local).await?
+/// ```
+#[rustfmt::skip]
+pub async fn run(f: Arc<Argv>, local: ThreadLocal) -> Result<()> {
+    let listen = match f.tls_listen {
+        Some(a) => a,
+        None => return Ok(())
+    };
+
+    let (cert, key) = match (&f.tls_cert, &f.tls_key) {
+        (Some(c), Some(k)) => (c, k),
+        _ => bail!("tls listener requires tls cert and tls key!")
+    };
+
+    let acceptor = TlsAcceptor::from(Arc::new(load_config(cert, key)?));
+    let listener = TcpListener::bind(listen).await?;
+    tokio::spawn(async move {
+        loop {
+            let (stream, addr) = match listener.accept().await {
+                Ok(s) => s,
+                Err(_) => continue
+            };
+
+            let acceptor = acceptor.clone();
+            let local = local.clone();
+            tokio::spawn(async move {
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    session(stream, addr, local).await;
+                }
+            });
+        }
+    });
+
+    log::info!(
+        "tls bind to {}",
+        listen
+    );
+
+    Ok(())
+}
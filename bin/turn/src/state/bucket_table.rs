@@ -40,6 +40,21 @@ impl BucketTable {
             .alloc()
     }
 
+    /// allocate an even port to the bucket.
+    ///
+    /// ```no_run
+    /// let buckets = BucketTable::new();
+    /// // buckets.alloc_even(0, true).await.is_some()
+    /// ```
+    pub async fn alloc_even(&self, group: u32, reserve: bool) -> Option<u16> {
+        self.raw
+            .lock()
+            .await
+            .entry(group)
+            .or_insert_with(Bucket::new)
+            .alloc_even(reserve)
+    }
+
     /// remove an allocated from the bucket.
     /// 
     /// ```no_run
@@ -86,6 +101,21 @@ impl Bucket {
         port
     }
 
+    /// allocated an even port to the bucket.
+    ///
+    /// the reserved next-higher port is counted
+    /// as an allocated port.
+    ///
+    /// ```no_run
+    /// let mut bucket = Bucket::new();
+    /// // bucket.alloc_even(true).is_some()
+    /// ```
+    pub fn alloc_even(&mut self, reserve: bool) -> Option<u16> {
+        let port = self.port.alloc_even(reserve)?;
+        self.num += if reserve { 2 } else { 1 };
+        Some(port)
+    }
+
     /// remove an allocated from the bucket.
     ///
     /// if the remove is successful, 
@@ -1,17 +1,32 @@
 mod bucket_table;
 mod random_port;
 mod nonce_table;
+mod permission;
+mod throttle;
 mod channel;
+mod relay;
+mod peer;
 mod node;
 
 use node::Node;
+pub use node::Transport;
+pub use relay::Relay;
+use throttle::Throttle;
 use channel::Channel;
+use permission::Permission;
 use nonce_table::NonceTable;
 use bucket_table::BucketTable;
 use stun::util::long_key;
+use stun::attribute::ErrKind;
+use stun::attribute::ErrKind::{
+    AllocationQuotaReached,
+    InsufficientCapacity
+};
 use tokio::sync::RwLock;
+use tokio::net::UdpSocket;
 use tokio::time::{
     Duration,
+    Instant,
     sleep
 };
 
@@ -28,6 +43,12 @@ use super::{
 
 type Addr = Arc<SocketAddr>;
 
+/// the lifetime of a reserved port in seconds.
+const RESERVATION_LIFETIME: u64 = 30;
+
+/// the lifetime of a cached allocate response in seconds.
+const TRANSACTION_LIFETIME: u64 = 40;
+
 /// Single State Tree.
 ///
 /// this state management example maintains the status of all 
@@ -45,9 +66,10 @@ pub struct State {
     buckets: BucketTable,
     nodes: RwLock<HashMap<Addr, Node>>,
     ports: RwLock<HashMap<(u32, u16), Addr>>,
-    port_bonds: RwLock<HashMap<Addr, HashMap<Addr, u16>>>,
+    port_bonds: RwLock<HashMap<Addr, HashMap<Addr, Permission>>>,
     channels: RwLock<HashMap<(u32, u16), Channel>>,
     channel_bonds: RwLock<HashMap<(Addr, u16), Addr>>,
+    reservations: RwLock<HashMap<u64, (u32, u16, Instant)>>,
 }
 
 impl State {
@@ -85,9 +107,9 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// // state.get_key(&addr, "panda")
+    /// // state.get_key(&addr, "panda", Transport::Udp)
     /// ```
-    pub async fn get_key(&self, a: &Addr, u: &str) -> Option<Arc<[u8; 16]>> {
+    pub async fn get_key(&self, a: &Addr, u: &str, t: Transport) -> Option<Arc<[u8; 16]>> {
         let key = self.nodes
             .read()
             .await
@@ -102,15 +124,22 @@ impl State {
             Err(_) => return None
         };
         
-        let node = Node::new(
+        let mut node = Node::new(
             auth.group, 
             long_key(
                 u, 
                 &auth.password, 
                 &self.conf.realm
-            )
+            ),
+            t
         );
 
+        node.alternate = auth.alternate;
+        node.username = u.to_string();
+        node.throttle = auth.bandwidth
+            .or(self.conf.bandwidth)
+            .map(Throttle::new);
+
         let key = node.get_password();
         self.nodes
             .write()
@@ -119,9 +148,236 @@ impl State {
         Some(key)
     }
 
+    /// get the alternate server of the node SocketAddr.
+    ///
+    /// the control service gives it with the key, the node
+    /// is then redirected instead of being allocated.
+    ///
+    /// it is cached on the node from the same reply as the
+    /// key, see `get_key`, so no other request is made.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// // state.get_alternate(&addr)
+    /// ```
+    pub async fn get_alternate(&self, a: &Addr) -> Option<SocketAddr> {
+        self.nodes.read().await.get(a)?.alternate
+    }
+
+    /// get the cached allocate response of the node SocketAddr.
+    ///
+    /// a retransmitted Allocate request has the same 5-tuple and
+    /// transaction id as the request that created the allocation,
+    /// it is answered with the same success response for 40 seconds
+    /// instead of a 437 (Allocation Mismatch) error.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    /// let mut response = vec![0u8; 20];
+    /// response[8..20].copy_from_slice(b"transaction!");
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.set_transaction(&addr, &response);
+    /// assert_eq!(state.get_transaction(&addr, b"transaction!"), Some(response));
+    /// assert_eq!(state.get_transaction(&addr, b"another one!"), None);
+    /// ```
+    #[rustfmt::skip]
+    pub async fn get_transaction(&self, a: &Addr, token: &[u8]) -> Option<Vec<u8>> {
+        let nodes = self.nodes.read().await;
+        let (buf, timer) = nodes.get(a)?.transaction.as_ref()?;
+        if &buf[8..20] != token || timer.elapsed().as_secs() >= TRANSACTION_LIFETIME {
+            return None
+        }
+
+        Some(buf.clone())
+    }
+
+    /// cache the allocate response of the node SocketAddr.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert!(state.set_transaction(&addr, &[0u8; 20]).is_some());
+    /// ```
+    pub async fn set_transaction(&self, a: &Addr, buf: &[u8]) -> Option<()> {
+        let mut nodes = self.nodes.write().await;
+        nodes.get_mut(a)?.transaction = Some((buf.to_vec(), Instant::now()));
+        Some(())
+    }
+
+    /// get the transport of the node SocketAddr.
+    ///
+    /// the data relayed to the node is sent through it.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Tls);
+    /// assert_eq!(state.get_transport(&addr), Some(Transport::Tls));
+    /// ```
+    pub async fn get_transport(&self, a: &Addr) -> Option<Transport> {
+        self.nodes.read().await.get(a).map(|n| n.transport)
+    }
+
+    /// whether the relayed bytes of the node are allowed.
+    ///
+    /// the bytes are taken from the bandwidth throttle 
+    /// of the node, the node without throttle is not limited.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// // state.is_allowed(&addr, 1024)
+    /// ```
+    pub async fn is_allowed(&self, a: &Addr, size: usize) -> bool {
+        match self.nodes.read().await.get(a) {
+            Some(n) => n.throttle.as_ref().map(|t| t.take(size)).unwrap_or(true),
+            None => false
+        }
+    }
+
+    /// whether the node already holds an allocation.
+    ///
+    /// a retransmitted or repeated Allocate request on the
+    /// same 5-tuple must not take another relayed port.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert!(!state.has_allocation(&addr));
+    ///
+    /// // the first allocate takes a port, the second is rejected
+    /// // with 437 (Allocation Mismatch) and takes nothing.
+    /// state.alloc_port(&addr);
+    /// assert!(state.has_allocation(&addr));
+    /// assert_eq!(state.get_allocations().get("panda"), Some(&1));
+    /// ```
+    pub async fn has_allocation(&self, a: &Addr) -> bool {
+        self.nodes
+            .read()
+            .await
+            .get(a)
+            .map(|n| !n.ports.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// get the allocations count of the users.
+    ///
+    /// a node is counted once it holds a relayed port,
+    /// the users without allocation are not listed.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.alloc_port(&addr);
+    /// assert_eq!(state.get_allocations().get("panda"), Some(&1));
+    /// ```
+    pub async fn get_allocations(&self) -> HashMap<String, usize> {
+        let mut allocations = HashMap::new();
+        for n in self.nodes.read().await.values() {
+            if !n.ports.is_empty() {
+                *allocations.entry(n.username.clone()).or_insert(0) += 1;
+            }
+        }
+
+        allocations
+    }
+
+    /// whether a new allocation of the node exceeds the quota.
+    ///
+    /// all the allocations of the username are counted,
+    /// including the one held by the node itself. it is
+    /// checked under the lock the port is allocated with,
+    /// so that concurrent requests cannot both pass it.
+    #[rustfmt::skip]
+    fn is_over_quota(&self, nodes: &HashMap<Addr, Node>, a: &Addr) -> bool {
+        let quota = match self.conf.quota {
+            Some(q) => q,
+            None => return false
+        };
+
+        let node = match nodes.get(a) {
+            Some(n) => n,
+            None => return false
+        };
+
+        nodes
+            .values()
+            .filter(|n| !n.ports.is_empty())
+            .filter(|n| n.username == node.username)
+            .count() >= quota
+    }
+
     /// obtain the peer address bound to the current 
     /// node according to the channel number.
     ///
+    /// an expired channel is not bound anymore, even before
+    /// it is removed by the poll. the channel only carries
+    /// data while the permissions are installed, they last
+    /// 5 minutes and the channel 10 minutes.
+    ///
     /// ```no_run
     /// use std::net::SocketAddr;
     /// use std::sync::Arc;
@@ -135,18 +391,37 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.get_key(&peer, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
     ///
     /// let addr_port = state.alloc_port(&addr).unwrap();
     /// let peer_port = state.alloc_port(&peer).unwrap();
     ///
     /// state.bind_channel(&addr, peer_port, 0x4000);
+    /// state.bind_port(&addr, peer_port);
     /// state.bind_channel(&peer, addr_port, 0x4000);
+    /// state.bind_port(&peer, addr_port);
     ///
     /// assert_eq!(state.get_channel_bond(&addr, 0x4000).unwrap(), peer);
+    /// assert_eq!(state.get_bond_port(&peer, &addr), Some(peer_port));
+    ///
+    /// // after 5 minutes the permission lapses while the channel
+    /// // is still bound, so the channel data is dropped, after
+    /// // 10 minutes the channel is not bound anymore.
+    /// // state.get_bond_port(&peer, &addr) == None
+    /// // state.get_channel_bond(&addr, 0x4000) == None
     /// ```
+    #[rustfmt::skip]
     pub async fn get_channel_bond(&self, a: &Addr, c: u16) -> Option<Addr> {
+        let g = self.nodes
+            .read()
+            .await
+            .get(a)?
+            .group;
+        if self.channels.read().await.get(&(g, c))?.is_death() {
+            return None
+        }
+
         self.channel_bonds
             .read()
             .await
@@ -170,8 +445,8 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.get_key(&peer, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
     ///
     /// let addr_port = state.alloc_port(&addr).unwrap();
     /// let peer_port = state.alloc_port(&peer).unwrap();
@@ -210,8 +485,8 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.get_key(&peer, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
     ///
     /// let addr_port = state.alloc_port(&addr).unwrap();
     /// let peer_port = state.alloc_port(&peer).unwrap();
@@ -228,7 +503,8 @@ impl State {
             .await
             .get(p)?
             .get(a)
-            .copied()
+            .filter(|p| !p.is_death())
+            .map(|p| p.port)
     }
    
     /// alloc a port from State.
@@ -272,6 +548,11 @@ impl State {
     /// to-expiry is recomputed with each successful Refresh request, and
     /// thus, the value computed here applies only until the first refresh.
     ///
+    /// the allocation is refused with 486 (Allocation Quota Reached)
+    /// when the username holds its quota of allocations, and with
+    /// 508 (Insufficient Capacity) when no port is available, the
+    /// quota is counted and the port taken under the same lock.
+    ///
     /// ```no_run
     /// use std::net::SocketAddr;
     /// use std::sync::Arc;
@@ -285,19 +566,24 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.get_key(&peer, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
     ///
-    /// assert!(state.alloc_port(&addr).unwrap().is_some());
-    /// assert!(state.alloc_port(&peer).unwrap().is_some());
+    /// assert!(state.alloc_port(&addr).is_ok());
+    /// assert!(state.alloc_port(&peer).is_ok());
     /// ```
     #[rustfmt::skip]
-    pub async fn alloc_port(&self, a: &Addr) -> Option<u16> {
+    pub async fn alloc_port(&self, a: &Addr) -> Result<u16, ErrKind> {
         let mut nodes = self.nodes.write().await;
-        let node = nodes.get_mut(a)?;
+        if self.is_over_quota(&nodes, a) {
+            return Err(AllocationQuotaReached)
+        }
+
+        let node = nodes.get_mut(a).ok_or(InsufficientCapacity)?;
         let port = self.buckets
             .alloc(node.group)
-            .await?;
+            .await
+            .ok_or(InsufficientCapacity)?;
         self.ports
             .write()
             .await
@@ -306,9 +592,192 @@ impl State {
             node.ports.push(port);    
         }
         
-        Some(port)
+        Ok(port)
     }
     
+    /// alloc an even port from State.
+    ///
+    /// If the client includes an EVEN-PORT attribute with the R bit set,
+    /// the server allocates an even port and reserves the next-higher
+    /// port for a subsequent allocation, the reservation is identified
+    /// by the returned token and expires after 30 seconds.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let peer = "127.0.0.1:8081".parse::<SocketAddr>().unwrap();
+    ///
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
+    ///
+    /// let (port, token) = state.alloc_even_port(&addr, true).unwrap();
+    /// assert_eq!(state.redeem_port(&peer, token.unwrap()), Ok(port + 1));
+    /// ```
+    #[rustfmt::skip]
+    pub async fn alloc_even_port(&self, a: &Addr, reserve: bool) -> Result<(u16, Option<u64>), ErrKind> {
+        let mut nodes = self.nodes.write().await;
+        if self.is_over_quota(&nodes, a) {
+            return Err(AllocationQuotaReached)
+        }
+
+        let node = nodes.get_mut(a).ok_or(InsufficientCapacity)?;
+        let port = self.buckets
+            .alloc_even(node.group, reserve)
+            .await
+            .ok_or(InsufficientCapacity)?;
+        self.ports
+            .write()
+            .await
+            .insert((node.group, port), a.clone());
+        node.ports.push(port);
+
+        let token = if reserve {
+            let token = rand::random::<u64>();
+            self.reservations
+                .write()
+                .await
+                .insert(token, (node.group, port + 1, Instant::now()));
+            Some(token)
+        } else {
+            None
+        };
+
+        Ok((port, token))
+    }
+
+    /// alloc the port held in reserve by a token.
+    ///
+    /// the token can only be redeemed once, by a node of the
+    /// same group and before the reservation expires.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert!(state.redeem_port(&addr, 0).is_err());
+    /// ```
+    #[rustfmt::skip]
+    pub async fn redeem_port(&self, a: &Addr, token: u64) -> Result<u16, ErrKind> {
+        let mut nodes = self.nodes.write().await;
+        if self.is_over_quota(&nodes, a) {
+            return Err(AllocationQuotaReached)
+        }
+
+        let node = nodes.get_mut(a).ok_or(InsufficientCapacity)?;
+        let mut reservations = self.reservations.write().await;
+        let (g, port, timer) = *reservations.get(&token).ok_or(InsufficientCapacity)?;
+        if g != node.group || timer.elapsed().as_secs() >= RESERVATION_LIFETIME {
+            return Err(InsufficientCapacity)
+        }
+
+        reservations.remove(&token);
+        self.ports
+            .write()
+            .await
+            .insert((g, port), a.clone());
+        node.ports.push(port);
+        Ok(port)
+    }
+
+    /// release a port of the node.
+    ///
+    /// the port is given back when the relay socket of the
+    /// allocation cannot be bound to it.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// let port = state.alloc_port(&addr).unwrap();
+    /// state.release_port(&addr, port);
+    /// assert!(!state.has_allocation(&addr));
+    /// ```
+    #[rustfmt::skip]
+    pub async fn release_port(&self, a: &Addr, port: u16) {
+        let mut nodes = self.nodes.write().await;
+        let node = match nodes.get_mut(a) {
+            Some(n) => n,
+            None => return
+        };
+
+        node.ports.retain(|p| *p != port);
+        self.ports.write().await.remove(&(node.group, port));
+        self.buckets.remove(node.group, port).await;
+    }
+
+    /// set the relay socket of the node.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// let socket = Arc::new(UdpSocket::bind("127.0.0.1:49152").await?);
+    /// let relay = Relay::new(socket, tokio::spawn(async {}));
+    /// assert!(state.set_relay(&addr, relay).is_some());
+    /// ```
+    pub async fn set_relay(&self, a: &Addr, relay: Relay) -> Option<()> {
+        self.nodes.write().await.get_mut(a)?.relay = Some(relay);
+        Some(())
+    }
+
+    /// get the relay socket of the node.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert!(state.get_relay(&addr).is_none());
+    /// ```
+    pub async fn get_relay(&self, a: &Addr) -> Option<Arc<UdpSocket>> {
+        self.nodes
+            .read()
+            .await
+            .get(a)?
+            .relay
+            .as_ref()
+            .map(|r| r.socket.clone())
+    }
+
     /// bind port for State.
     ///
     /// A server need not do anything special to implement
@@ -316,6 +785,9 @@ impl State {
     /// "stateless stack approach".  Retransmitted CreatePermission
     /// requests will simply refresh the permissions.
     ///
+    /// the permission expires after 5 minutes unless refreshed,
+    /// data from the peer is dropped once it has expired.
+    ///
     /// ```no_run
     /// use std::net::SocketAddr;
     /// use std::sync::Arc;
@@ -329,8 +801,8 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.get_key(&peer, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
     ///
     /// let addr_port = state.alloc_port(&addr).unwrap();
     /// let peer_port = state.alloc_port(&peer).unwrap();
@@ -356,7 +828,8 @@ impl State {
             .entry(a.clone())
             .or_insert_with(|| HashMap::with_capacity(10))
             .entry(p)
-            .or_insert(port);
+            .and_modify(|p| p.refresh())
+            .or_insert_with(|| Permission::new(port));
         Some(())
     }
 
@@ -385,8 +858,8 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.get_key(&peer, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.get_key(&peer, "panda", Transport::Udp);
     ///
     /// let addr_port = state.alloc_port(&addr).unwrap();
     /// let peer_port = state.alloc_port(&peer).unwrap();
@@ -397,11 +870,28 @@ impl State {
     #[rustfmt::skip]
     pub async fn bind_channel(&self, a: &Addr, p: u16, c: u16) -> Option<()> {
         let ports = self.ports.read().await;
+        let mut channel_bonds = self.channel_bonds.write().await;
         let mut channels = self.channels.write().await;
         let mut nodes = self.nodes.write().await;
         let mut is_empty = false;
 
         let node = nodes.get_mut(a)?;
+        let source = ports.get(&(node.group, p))?;
+        if node.peers.get_channel(c).is_some() {
+            return None
+        }
+
+        // the same channel cannot be bound to two different
+        // transport addresses, nor can the same transport address
+        // be bound to two different channels.
+        let is_conflict = channel_bonds
+            .iter()
+            .filter(|((k, _), _)| k == a)
+            .any(|((_, n), v)| (*n == c) != (v == source));
+        if is_conflict {
+            return None
+        }
+
         let channel = channels
             .entry((node.group, c))
             .or_insert_with(|| {
@@ -427,19 +917,159 @@ impl State {
             channel.refresh();
         }
 
-        let source = ports.get(&(node.group, p))?;
         if !node.channels.contains(&c) {
             node.channels.push(c)
         }
 
-        self.channel_bonds
-            .write()
-            .await
+        channel_bonds
             .entry((a.clone(), c))
             .or_insert_with(|| source.clone());
         Some(())
     }
 
+    /// install or refresh the permission of a peer outside the group.
+    ///
+    /// the permission is installed for the IP address of the peer,
+    /// the node must hold an allocation.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let peer = "1.1.1.1:8081".parse::<SocketAddr>().unwrap();
+    ///
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert!(state.bind_peer(&addr, &peer).is_none());
+    /// state.alloc_port(&addr);
+    /// assert!(state.bind_peer(&addr, &peer).is_some());
+    /// assert!(state.has_peer(&addr, &peer));
+    /// ```
+    pub async fn bind_peer(&self, a: &Addr, p: &SocketAddr) -> Option<()> {
+        self.nodes
+            .write()
+            .await
+            .get_mut(a)
+            .filter(|n| !n.ports.is_empty())?
+            .peers
+            .bind_permission(p);
+        Some(())
+    }
+
+    /// whether the permission of a peer outside the group is installed.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let peer = "1.1.1.1:8081".parse::<SocketAddr>().unwrap();
+    ///
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert!(!state.has_peer(&addr, &peer));
+    /// ```
+    pub async fn has_peer(&self, a: &Addr, p: &SocketAddr) -> bool {
+        self.nodes
+            .read()
+            .await
+            .get(a)
+            .map(|n| n.peers.has_permission(p))
+            .unwrap_or(false)
+    }
+
+    /// bind channel number to a peer outside the group.
+    ///
+    /// the channel number cannot be bound to a peer of the group
+    /// at the same time, the channel binding also installs or
+    /// refreshes the permission towards the peer.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let peer = "1.1.1.1:8081".parse::<SocketAddr>().unwrap();
+    ///
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.alloc_port(&addr);
+    /// assert!(state.bind_peer_channel(&addr, &peer, 0x4000).is_some());
+    /// assert_eq!(state.get_peer_channel(&addr, 0x4000), Some(peer));
+    /// assert_eq!(state.get_peer_channel_number(&addr, &peer), Some(0x4000));
+    /// ```
+    #[rustfmt::skip]
+    pub async fn bind_peer_channel(&self, a: &Addr, p: &SocketAddr, c: u16) -> Option<()> {
+        let mut nodes = self.nodes.write().await;
+        let node = nodes
+            .get_mut(a)
+            .filter(|n| !n.ports.is_empty())?;
+        if node.channels.contains(&c) || !node.peers.bind_channel(p, c) {
+            return None
+        }
+
+        node.peers.bind_permission(p);
+        Some(())
+    }
+
+    /// get the peer outside the group bound to the channel number.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert_eq!(state.get_peer_channel(&addr, 0x4000), None);
+    /// ```
+    pub async fn get_peer_channel(&self, a: &Addr, c: u16) -> Option<SocketAddr> {
+        self.nodes.read().await.get(a)?.peers.get_channel(c)
+    }
+
+    /// get the channel number bound to a peer outside the group.
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    /// use std::sync::Arc;
+    /// use turn::argv::Argv;
+    /// use turn::broker::Broker;
+    ///
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    /// let peer = "1.1.1.1:8081".parse::<SocketAddr>().unwrap();
+    ///
+    /// let argvure = Argv::generate().unwrap();
+    /// let broker = Broker::new(&argvure);
+    /// let state = State::new(&argvure, &broker);
+    ///
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// assert_eq!(state.get_peer_channel_number(&addr, &peer), None);
+    /// ```
+    pub async fn get_peer_channel_number(&self, a: &Addr, p: &SocketAddr) -> Option<u16> {
+        self.nodes.read().await.get(a)?.peers.get_channel_number(p)
+    }
+
     /// refresh node lifetime.
     ///
     /// The server computes a value called the "desired lifetime" as follows:
@@ -486,17 +1116,26 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
-    /// state.refresh(&addr, 600);
-    /// state.refresh(&addr, 0);
+    /// state.get_key(&addr, "panda", Transport::Udp);
+    /// state.alloc_port(&addr);
+    /// assert!(state.refresh(&addr, 600).is_some());
+    /// assert!(state.refresh(&addr, 0).is_some());
+    /// assert!(state.refresh(&addr, 0).is_none());
     /// ```
     #[rustfmt::skip]
-    pub async fn refresh(&self, a: &Addr, delay: u32) {
-        if delay == 0 { 
-            self.remove(a).await; 
-        } else if let Some(n) = self.nodes.write().await.get_mut(a) {
-            n.set_lifetime(delay);
+    pub async fn refresh(&self, a: &Addr, delay: u32) -> Option<()> {
+        let mut nodes = self.nodes.write().await;
+        let node = nodes
+            .get_mut(a)
+            .filter(|n| !n.ports.is_empty())?;
+        if delay > 0 {
+            node.set_lifetime(delay);
+            return Some(())
         }
+
+        drop(nodes);
+        self.remove(a).await;
+        Some(())
     }
 
     /// remove a node.
@@ -512,7 +1151,7 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
     /// state.remove(&addr);
     /// ```
     #[rustfmt::skip]
@@ -533,11 +1172,14 @@ impl State {
             self.remove_channel(node.group, c).await;
         }
 
+        // the permissions installed by the peers
+        // towards the node are released as well.
         self.nonces.remove(a).await;
-        self.port_bonds
-            .write()
-            .await
-            .remove(a);
+        let mut port_bonds = self.port_bonds.write().await;
+        port_bonds.remove(a);
+        for bonds in port_bonds.values_mut() {
+            bonds.remove(a);
+        }
     }
     
     /// remove channel in State. 
@@ -553,7 +1195,7 @@ impl State {
     /// let broker = Broker::new(&argvure);
     /// let state = State::new(&argvure, &broker);
     ///
-    /// state.get_key(&addr, "panda");
+    /// state.get_key(&addr, "panda", Transport::Udp);
     /// assert!(state.remove_channel(0, 0x4000).is_none());
     /// ```
     #[rustfmt::skip]
@@ -612,6 +1254,30 @@ impl State {
         for (g, c) in fail_channels {
             self.remove_channel(g, c).await;
         }
+
+        self.port_bonds
+            .write()
+            .await
+            .values_mut()
+            .for_each(|b| b.retain(|_, p| !p.is_death()));
+        self.nodes
+            .write()
+            .await
+            .values_mut()
+            .for_each(|n| n.peers.poll());
+        let fail_reservations = self.reservations
+            .read()
+            .await
+            .iter()
+            .filter(|(_, (_, _, t))| t.elapsed().as_secs() >= RESERVATION_LIFETIME)
+            .map(|(k, v)| (*k, v.0, v.1))
+            .collect::<Vec<(u64, u32, u16)>>();
+        for (k, g, p) in fail_reservations {
+            self.reservations.write().await.remove(&k);
+            self.buckets.remove(g, p).await;
+        }
+
+        self.nonces.poll().await;
     }
 
     /// auto run state poll.
@@ -647,6 +1313,7 @@ impl State {
             buckets: BucketTable::new(),
             nonces: NonceTable::new(),
             channel_bonds: create_table(),
+            reservations: create_table(),
             channels: create_table(),
             port_bonds: create_table(),
             ports: create_table(),
@@ -1,5 +1,24 @@
+use super::{
+    throttle::Throttle,
+    relay::Relay,
+    peer::Peers
+};
 use tokio::time::Instant;
-use std::sync::Arc;
+use std::{
+    net::SocketAddr,
+    sync::Arc
+};
+
+/// the transport a node is connected over.
+///
+/// the responses and the relayed data are sent to a node
+/// through the transport it arrived on.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Transport {
+    Udp,
+    Tls,
+    Dtls,
+}
 
 /// turn node session.
 ///
@@ -8,10 +27,24 @@ use std::sync::Arc;
 /// * the channel alloc table.
 /// * the group number.
 /// * the time-to-expiry for each relayed transport address.
+/// * the alternate server given by the control service.
+/// * the username of the allocation.
+/// * the relayed bandwidth throttle.
+/// * the transport of the node.
+/// * the allocate success response of the node.
+/// * the relay socket of the allocation.
+/// * the peers outside the group.
 pub struct Node {
     pub channels: Vec<u16>,
     pub ports: Vec<u16>,
     pub group: u32,
+    pub username: String,
+    pub alternate: Option<SocketAddr>,
+    pub throttle: Option<Throttle>,
+    pub transport: Transport,
+    pub transaction: Option<(Vec<u8>, Instant)>,
+    pub relay: Option<Relay>,
+    pub peers: Peers,
     timer: Instant,
     lifetime: u64,
     password: Arc<[u8; 16]>
@@ -20,19 +53,26 @@ pub struct Node {
 impl Node {
     /// create node session.
     ///
-    /// node session from group number and long key.
+    /// node session from group number, long key and transport.
     ///
     /// ```no_run
     /// let key = stun::util::long_key("panda", "panda", "raspberry");
-    /// // Node::new(0, key.clone());
+    /// // Node::new(0, key.clone(), Transport::Udp);
     /// ```
-    pub fn new(group: u32, password: [u8; 16]) -> Self {
+    pub fn new(group: u32, password: [u8; 16], transport: Transport) -> Self {
         Self {
             channels: Vec::with_capacity(5),
             ports: Vec::with_capacity(10),
             timer: Instant::now(),
             password: Arc::new(password),
             lifetime: 600,
+            alternate: None,
+            username: String::new(),
+            throttle: None,
+            transaction: None,
+            relay: None,
+            peers: Peers::default(),
+            transport,
             group,
         }
     }
@@ -43,7 +83,7 @@ impl Node {
     ///
     /// ```no_run
     /// let key = stun::util::long_key("panda", "panda", "raspberry");
-    /// let mut node = Node::new(0, key.clone());
+    /// let mut node = Node::new(0, key.clone(), Transport::Udp);
     /// node.set_lifetime(600);
     /// ```
     pub fn set_lifetime(&mut self, delay: u32) {
@@ -55,7 +95,7 @@ impl Node {
     ///
     /// ```no_run
     /// let key = stun::util::long_key("panda", "panda", "raspberry");
-    /// let mut node = Node::new(0, key.clone());
+    /// let mut node = Node::new(0, key.clone(), Transport::Udp);
     /// node.set_lifetime(600);
     /// assert!(!node.is_death());
     /// ```
@@ -71,7 +111,7 @@ impl Node {
     ///
     /// ```no_run
     /// let key = stun::util::long_key("panda", "panda", "raspberry");
-    /// let node = Node::new(0, key.clone());
+    /// let node = Node::new(0, key.clone(), Transport::Udp);
     /// assert_eq!(!node.get_password(), Arc::new(key));
     /// ```
     pub fn get_password(&self) -> Arc<[u8; 16]> {
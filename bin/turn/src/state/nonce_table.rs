@@ -15,6 +15,9 @@ use rand::{
     Rng
 };
 
+/// nonce lifetime in seconds.
+const LIFETIME: u64 = 3600;
+
 /// Session nonce.
 ///
 /// The NONCE attribute may be present in requests and responses.  It
@@ -45,7 +48,9 @@ impl NonceTable {
     
     /// get session nonce string.
     ///
-    /// each node is assigned a random string valid for 1 hour.
+    /// each node is assigned a random string valid for 1 hour,
+    /// an expired nonce is replaced by a new one, so a request
+    /// presenting the old nonce is answered with 438 (Stale Nonce).
     ///
     /// ```no_run
     /// use std::net::SocketAddr;
@@ -61,12 +66,26 @@ impl NonceTable {
             }
         }
 
-        self.raw
-            .write()
-            .await
-            .entry(a.clone())
-            .or_insert_with(Nonce::new)
-            .unwind()
+        let mut raw = self.raw.write().await;
+        let nonce = raw.entry(a.clone()).or_insert_with(Nonce::new);
+        if nonce.is_death() {
+            *nonce = Nonce::new();
+        }
+
+        nonce.unwind()
+    }
+
+    /// remove expired session nonce strings.
+    ///
+    /// the nonces of the nodes that never authenticated
+    /// are only released here.
+    ///
+    /// ```no_run
+    /// let nonce_table = NonceTable::new();
+    /// nonce_table.poll();
+    /// ```
+    pub async fn poll(&self) {
+        self.raw.write().await.retain(|_, n| !n.is_death());
     }
 
     /// remove session nonce string.
@@ -99,7 +118,7 @@ impl Nonce {
     /// assert!(!nonce.is_death());
     /// ```
     pub fn is_death(&self) -> bool {
-        self.timer.elapsed().as_secs() < 3600
+        self.timer.elapsed().as_secs() >= LIFETIME
     }
 
     /// unwind nonce random string.
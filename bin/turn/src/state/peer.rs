@@ -0,0 +1,131 @@
+use super::permission::Permission;
+use tokio::time::Instant;
+use std::{
+    collections::HashMap,
+    net::{
+        IpAddr,
+        SocketAddr
+    }
+};
+
+/// the lifetime of a channel binding in seconds.
+const CHANNEL_LIFETIME: u64 = 600;
+
+/// the peers of a node outside its group.
+///
+/// the peers outside the group are reached through the relay
+/// socket of the allocation, the permissions are installed for
+/// their IP address, and the channels are bound to their
+/// transport address, both expire unless refreshed.
+#[derive(Default)]
+pub struct Peers {
+    permissions: HashMap<IpAddr, Permission>,
+    channels: HashMap<u16, (SocketAddr, Instant)>,
+}
+
+impl Peers {
+    /// install or refresh the permission of the peer.
+    ///
+    /// ```no_run
+    /// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+    /// let mut peers = Peers::default();
+    /// peers.bind_permission(&peer);
+    /// assert!(peers.has_permission(&peer));
+    /// ```
+    pub fn bind_permission(&mut self, a: &SocketAddr) {
+        self.permissions
+            .entry(a.ip())
+            .and_modify(|p| p.refresh())
+            .or_insert_with(|| Permission::new(a.port()));
+    }
+
+    /// whether the permission of the peer is installed.
+    ///
+    /// the port of the peer is ignored.
+    ///
+    /// ```no_run
+    /// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+    /// let other = "1.1.1.1:8081".parse::<SocketAddr>().unwrap();
+    /// let mut peers = Peers::default();
+    /// assert!(!peers.has_permission(&peer));
+    /// peers.bind_permission(&peer);
+    /// assert!(peers.has_permission(&other));
+    /// ```
+    pub fn has_permission(&self, a: &SocketAddr) -> bool {
+        self.permissions
+            .get(&a.ip())
+            .map(|p| !p.is_death())
+            .unwrap_or(false)
+    }
+
+    /// bind or refresh the channel of the peer.
+    ///
+    /// the same channel cannot be bound to two different
+    /// transport addresses, nor can the same transport address
+    /// be bound to two different channels.
+    ///
+    /// ```no_run
+    /// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+    /// let other = "1.1.1.1:8081".parse::<SocketAddr>().unwrap();
+    /// let mut peers = Peers::default();
+    /// assert!(peers.bind_channel(&peer, 0x4000));
+    /// assert!(peers.bind_channel(&peer, 0x4000));
+    /// assert!(!peers.bind_channel(&other, 0x4000));
+    /// assert!(!peers.bind_channel(&peer, 0x4001));
+    /// ```
+    #[rustfmt::skip]
+    pub fn bind_channel(&mut self, a: &SocketAddr, c: u16) -> bool {
+        let is_conflict = self.channels
+            .iter()
+            .filter(|(_, (_, t))| t.elapsed().as_secs() < CHANNEL_LIFETIME)
+            .any(|(n, (p, _))| (*n == c) != (p == a));
+        if is_conflict {
+            return false
+        }
+
+        self.channels.insert(c, (*a, Instant::now()));
+        true
+    }
+
+    /// get the peer bound to the channel.
+    ///
+    /// ```no_run
+    /// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+    /// let mut peers = Peers::default();
+    /// peers.bind_channel(&peer, 0x4000);
+    /// assert_eq!(peers.get_channel(0x4000), Some(peer));
+    /// ```
+    pub fn get_channel(&self, c: u16) -> Option<SocketAddr> {
+        self.channels
+            .get(&c)
+            .filter(|(_, t)| t.elapsed().as_secs() < CHANNEL_LIFETIME)
+            .map(|(p, _)| *p)
+    }
+
+    /// get the channel bound to the peer.
+    ///
+    /// ```no_run
+    /// let peer = "1.1.1.1:8080".parse::<SocketAddr>().unwrap();
+    /// let mut peers = Peers::default();
+    /// peers.bind_channel(&peer, 0x4000);
+    /// assert_eq!(peers.get_channel_number(&peer), Some(0x4000));
+    /// ```
+    pub fn get_channel_number(&self, a: &SocketAddr) -> Option<u16> {
+        self.channels
+            .iter()
+            .filter(|(_, (_, t))| t.elapsed().as_secs() < CHANNEL_LIFETIME)
+            .find(|(_, (p, _))| p == a)
+            .map(|(n, _)| *n)
+    }
+
+    /// remove the expired permissions and channels.
+    ///
+    /// ```no_run
+    /// let mut peers = Peers::default();
+    /// peers.poll();
+    /// ```
+    pub fn poll(&mut self) {
+        self.permissions.retain(|_, p| !p.is_death());
+        self.channels.retain(|_, (_, t)| t.elapsed().as_secs() < CHANNEL_LIFETIME);
+    }
+}
@@ -0,0 +1,54 @@
+use tokio::time::Instant;
+
+/// Peer permission.
+///
+/// Each allocation can have zero or more permissions.  Each permission
+/// consists of an IP address and a time-to-expiry.  The IP address
+/// describes a peer that is allowed to send data to the client, and the
+/// time-to-expiry is the number of seconds until the permission expires.
+///
+/// By sending either CreatePermission requests or ChannelBind requests,
+/// the client can cause the server to install or refresh a permission
+/// for a given IP address.  This causes one of two things to happen:
+///
+/// *  If no permission for that IP address exists, then a permission is
+///    created with the given IP address and a time-to-expiry equal to
+///    Permission Lifetime.
+///
+/// *  If a permission for that IP address already exists, then the time-
+///    to-expiry for that permission is reset to Permission Lifetime.
+///
+/// The Permission Lifetime MUST be 300 seconds (= 5 minutes).
+pub struct Permission {
+    pub port: u16,
+    timer: Instant,
+}
+
+impl Permission {
+    pub fn new(port: u16) -> Self {
+        Self {
+            timer: Instant::now(),
+            port,
+        }
+    }
+
+    /// refresh permission lifetime.
+    ///
+    /// ```no_run
+    /// let mut permission = Permission::new(49152);
+    /// permission.refresh();
+    /// ```
+    pub fn refresh(&mut self) {
+        self.timer = Instant::now();
+    }
+
+    /// whether the permission lifetime has ended.
+    ///
+    /// ```no_run
+    /// let permission = Permission::new(49152);
+    /// assert!(!permission.is_death());
+    /// ```
+    pub fn is_death(&self) -> bool {
+        self.timer.elapsed().as_secs() >= 300
+    }
+}
@@ -247,6 +247,70 @@ impl RandomPort {
         rng.gen_range(0, self.high as u16)
     }
 
+    /// random assign an even port.
+    ///
+    /// the next-higher port is assigned as well
+    /// when it is reserved.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use random_port::RandomPort;
+    ///
+    /// let range = 49152..65535;
+    /// let mut pool = RandomPort::new(range);
+    ///
+    /// let port = pool.alloc_even(true).unwrap();
+    /// assert_eq!(port % 2, 0);
+    /// assert!(!pool.is_free(port));
+    /// assert!(!pool.is_free(port + 1));
+    /// ```
+    pub fn alloc_even(&mut self, reserve: bool) -> Option<u16> {
+        let size = (self.range.end - self.range.start) as usize;
+        let start = self.random() as usize * 64;
+        let port = (0..size)
+            .map(|i| self.range.start + ((start + i) % size) as u16)
+            .filter(|p| p & 1 == 0)
+            .find(|p| self.is_free(*p) && (!reserve || self.is_free(p + 1)))?;
+
+        self.take(port);
+        if reserve {
+            self.take(port + 1);
+        }
+
+        Some(port)
+    }
+
+    /// whether the port is not assigned.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use random_port::RandomPort;
+    ///
+    /// let range = 49152..65535;
+    /// let mut pool = RandomPort::new(range);
+    ///
+    /// assert!(pool.is_free(49152));
+    /// assert_eq!(pool.alloc(Some(0)), Some(49152));
+    /// assert!(!pool.is_free(49152));
+    /// assert!(!pool.is_free(65535));
+    /// ```
+    pub fn is_free(&self, port: u16) -> bool {
+        if !self.range.contains(&port) {
+            return false
+        }
+
+        let offset = (port - self.range.start) as usize;
+        self.buckets[offset / 64] & (1 << (63 - offset % 64)) != 0
+    }
+
+    /// assign the given port.
+    fn take(&mut self, port: u16) {
+        let offset = (port - self.range.start) as usize;
+        self.write(offset / 64, offset % 64, Bit::Low)
+    }
+
     /// compute bucket size from range.
     ///
     /// # Unit Test
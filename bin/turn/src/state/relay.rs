@@ -0,0 +1,38 @@
+use tokio::{
+    net::UdpSocket,
+    task::JoinHandle
+};
+
+use std::sync::Arc;
+
+/// the relay socket of an allocation.
+///
+/// the relayed transport address is bound to the socket,
+/// the task reading it is stopped when the allocation is
+/// removed, and then the socket is closed.
+pub struct Relay {
+    pub socket: Arc<UdpSocket>,
+    task: JoinHandle<()>,
+}
+
+impl Relay {
+    /// create relay from the socket and the task reading it.
+    ///
+    /// ```no_run
+    /// let socket = Arc::new(UdpSocket::bind("127.0.0.1:49152").await?);
+    /// let task = tokio::spawn(async {});
+    /// let relay = Relay::new(socket, task);
+    /// ```
+    pub fn new(socket: Arc<UdpSocket>, task: JoinHandle<()>) -> Self {
+        Self {
+            socket,
+            task
+        }
+    }
+}
+
+impl Drop for Relay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -0,0 +1,83 @@
+use tokio::time::{
+    Duration,
+    Instant
+};
+
+use std::sync::Mutex;
+
+/// the size of the largest datagram.
+const MAX_PACKET: u64 = 65535;
+
+/// Relay bandwidth throttle.
+///
+/// a token bucket over the relayed bytes of an allocation,
+/// the bucket is refilled at the configured rate and holds
+/// at most one second of it, so that a short burst is allowed
+/// but one participant cannot saturate the node's uplink.
+/// it holds at least one largest datagram, which would be
+/// discarded forever otherwise under a low rate.
+pub struct Throttle {
+    rate: u64,
+    capacity: u64,
+    bucket: Mutex<(u64, Instant)>,
+}
+
+impl Throttle {
+    /// create throttle from rate (byte/s).
+    ///
+    /// ```no_run
+    /// let throttle = Throttle::new(1024 * 1024);
+    /// ```
+    pub fn new(rate: u64) -> Self {
+        let capacity = rate.max(MAX_PACKET);
+        Self {
+            bucket: Mutex::new((capacity, Instant::now())),
+            capacity,
+            rate,
+        }
+    }
+
+    /// take tokens for the relayed bytes.
+    ///
+    /// return false when the bucket does not hold enough
+    /// tokens, the datagram is then to be discarded.
+    ///
+    /// the timer only advances by the time the refilled
+    /// tokens stand for, so the remainder is not lost when
+    /// the datagrams are closer than one token apart.
+    ///
+    /// ```no_run
+    /// let throttle = Throttle::new(1024 * 1024);
+    /// assert!(throttle.take(1024 * 1024));
+    /// assert!(!throttle.take(1));
+    ///
+    /// // the bucket holds at least one largest datagram.
+    /// let throttle = Throttle::new(1024);
+    /// assert!(throttle.take(65535));
+    /// ```
+    #[rustfmt::skip]
+    pub fn take(&self, size: usize) -> bool {
+        let mut bucket = self.bucket.lock().unwrap();
+        let (tokens, timer) = &mut *bucket;
+        let elapsed = timer.elapsed().as_micros();
+        let refill = (elapsed * self.rate as u128 / 1_000_000) as u64;
+        if refill > 0 {
+            *tokens = tokens.saturating_add(refill);
+            if *tokens >= self.capacity {
+                *tokens = self.capacity;
+                *timer = Instant::now();
+            } else {
+                let used = refill as u128 * 1_000_000 / self.rate as u128;
+                *timer += Duration::from_micros(used as u64);
+            }
+        }
+
+        let size = size as u64;
+        if *tokens < size {
+            return false
+        }
+
+        *tokens -= size;
+        true
+    }
+}
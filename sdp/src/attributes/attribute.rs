@@ -0,0 +1,99 @@
+use super::Key;
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// attribute line.
+///
+/// a=<attribute-name>
+/// a=<attribute-name>:<attribute-value>
+///
+/// one "a=" line as it appeared in the description, see
+/// `Attributes::iter`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Attribute<'a> {
+    pub name: &'a str,
+    pub value: Option<&'a str>
+}
+
+impl<'a> Attribute<'a> {
+    /// get the attribute key, `None` for unknown attributes.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let attribute = Attribute::from("rtpmap:111 opus/48000/2");
+    /// assert_eq!(attribute.key(), Some(Key::RtpMap));
+    /// assert_eq!(Attribute::from("rtcp-mux").key(), None);
+    /// ```
+    pub fn key(&self) -> Option<Key> {
+        Key::try_from(self.name).ok()
+    }
+
+    /// get the payload type of a format attribute, "a=rtpmap:",
+    /// "a=fmtp:", "a=rtcp-fb:" or "a=framesize:", `None` for the
+    /// other attributes and for "a=rtcp-fb:*".
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(Attribute::from("rtpmap:111 opus/48000/2").format(), Some(111));
+    /// assert_eq!(Attribute::from("framesize:96 320-240").format(), Some(96));
+    /// assert_eq!(Attribute::from("rtcp-fb:* transport-cc").format(), None);
+    /// assert_eq!(Attribute::from("mid:0").format(), None);
+    /// ```
+    pub fn format(&self) -> Option<u8> {
+        match self.key()? {
+            Key::RtpMap | Key::Fmtp | Key::RtcpFb | Key::FrameSize => (),
+            _ => return None
+        }
+
+        self.value?.split(' ').next()?.parse().ok()
+    }
+}
+
+impl<'a> From<&'a str> for Attribute<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let attribute = Attribute::from("rtpmap:111 opus/48000/2");
+    /// assert_eq!(attribute.name, "rtpmap");
+    /// assert_eq!(attribute.value, Some("111 opus/48000/2"));
+    ///
+    /// let attribute = Attribute::from("sendrecv");
+    /// assert_eq!(attribute.name, "sendrecv");
+    /// assert_eq!(attribute.value, None);
+    /// ```
+    fn from(line: &'a str) -> Self {
+        match line.split_once(':') {
+            Some((name, value)) => Self { name, value: Some(value) },
+            None => Self { name: line, value: None }
+        }
+    }
+}
+
+impl<'a> fmt::Display for Attribute<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let attribute = Attribute::from("mid:0");
+    /// assert_eq!(format!("{}", attribute), "mid:0");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(value) = self.value {
+            write!(f, ":{}", value)?;
+        }
+
+        Ok(())
+    }
+}
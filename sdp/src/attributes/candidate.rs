@@ -0,0 +1,240 @@
+use crate::util::next_field;
+use anyhow::{
+    Result,
+    ensure,
+    anyhow
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// candidate type.
+///
+/// cand-type = "typ" SP candidate-types
+/// candidate-types = "host" / "srflx" / "prflx" / "relay" / token
+///
+/// the types not known to this crate are kept as `Other`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CandidateKind<'a> {
+    Host,
+    Srflx,
+    Prflx,
+    Relay,
+    Other(&'a str)
+}
+
+/// Name:  candidate
+/// Value:  candidate-value
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// candidate-attribute = "candidate" ":" foundation SP component-id SP
+///                       transport SP
+///                       priority SP
+///                       connection-address SP     ;from RFC 4566
+///                       port         ;port from RFC 4566
+///                       SP cand-type
+///                       [SP rel-addr]
+///                       [SP rel-port]
+///                       *(SP cand-extension)
+///
+/// Example:
+/// a=candidate:1 1 UDP 2130706431 203.0.113.141 8998 typ host
+///
+/// This attribute is used with Interactive Connectivity Establishment
+/// (ICE), and provides one of many possible candidate addresses for
+/// communication.  These addresses are validated with an end-to-end
+/// connectivity check using Session Traversal Utilities for NAT (STUN).
+///
+/// The connection address is kept as text, since besides IPv4 and IPv6
+/// addresses it can also carry a fully qualified domain name, such as
+/// the mDNS host names emitted by browsers.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Candidate<'a> {
+    pub foundation: &'a str,
+    pub component: u16,
+    pub transport: &'a str,
+    pub priority: u32,
+    pub address: &'a str,
+    pub port: u16,
+    pub kind: CandidateKind<'a>,
+    pub raddr: Option<&'a str>,
+    pub rport: Option<u16>,
+    pub extensions: Vec<(&'a str, &'a str)>
+}
+
+impl<'a> fmt::Display for Candidate<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let candidate = Candidate {
+    ///     foundation: "1",
+    ///     component: 1,
+    ///     transport: "UDP",
+    ///     priority: 1694498815,
+    ///     address: "192.0.2.33",
+    ///     port: 10000,
+    ///     kind: CandidateKind::Srflx,
+    ///     raddr: Some("10.0.1.1"),
+    ///     rport: Some(8998),
+    ///     extensions: vec![("generation", "0")]
+    /// };
+    ///
+    /// assert_eq!(
+    ///     format!("{}", candidate),
+    ///     "1 1 UDP 1694498815 192.0.2.33 10000 typ srflx raddr 10.0.1.1 rport 8998 generation 0"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} typ {}",
+            self.foundation,
+            self.component,
+            self.transport,
+            self.priority,
+            self.address,
+            self.port,
+            self.kind
+        )?;
+
+        if let Some(raddr) = self.raddr {
+            write!(f, " raddr {}", raddr)?;
+        }
+
+        if let Some(rport) = self.rport {
+            write!(f, " rport {}", rport)?;
+        }
+
+        for (k, v) in &self.extensions {
+            write!(f, " {} {}", k, v)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Candidate<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let candidate: Candidate = Candidate::try_from(
+    ///     "1 1 UDP 2130706431 203.0.113.141 8998 typ host generation 0"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(candidate.foundation, "1");
+    /// assert_eq!(candidate.component, 1);
+    /// assert_eq!(candidate.transport, "UDP");
+    /// assert_eq!(candidate.priority, 2130706431);
+    /// assert_eq!(candidate.address, "203.0.113.141");
+    /// assert_eq!(candidate.port, 8998);
+    /// assert_eq!(candidate.kind, CandidateKind::Host);
+    /// assert_eq!(candidate.raddr, None);
+    /// assert_eq!(candidate.rport, None);
+    /// assert_eq!(candidate.extensions, vec![("generation", "0")]);
+    ///
+    /// let candidate: Candidate = Candidate::try_from(
+    ///     "2 1 udp 1694498815 2001:db8::1 45664 typ srflx raddr :: rport 9"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(candidate.address, "2001:db8::1");
+    /// assert_eq!(candidate.kind, CandidateKind::Srflx);
+    /// assert_eq!(candidate.raddr, Some("::"));
+    /// assert_eq!(candidate.rport, Some(9));
+    ///
+    /// let value = "3 1 udp 1694498815 203.0.113.141 9 typ nat64 generation 0";
+    /// let candidate: Candidate = Candidate::try_from(value).unwrap();
+    /// assert_eq!(candidate.kind, CandidateKind::Other("nat64"));
+    /// assert_eq!(candidate.to_string(), value);
+    ///
+    /// assert!(Candidate::try_from("1 1 UDP 2130706431 203.0.113.141 8998").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut split = value.split(' ');
+        let mut candidate = Self {
+            foundation: next_field(&mut split, "invalid candidate!")?,
+            component: next_field(&mut split, "invalid candidate!")?.parse()?,
+            transport: next_field(&mut split, "invalid candidate!")?,
+            priority: next_field(&mut split, "invalid candidate!")?.parse()?,
+            address: next_field(&mut split, "invalid candidate!")?,
+            port: next_field(&mut split, "invalid candidate!")?.parse()?,
+            kind: {
+                ensure!(next_field(&mut split, "invalid candidate!")? == "typ", "invalid candidate!");
+                CandidateKind::try_from(next_field(&mut split, "invalid candidate!")?)?
+            },
+            raddr: None,
+            rport: None,
+            extensions: Vec::new()
+        };
+
+        while let Some(k) = split.next() {
+            let v = next_field(&mut split, "invalid candidate extension!")?;
+            match k {
+                "raddr" => candidate.raddr = Some(v),
+                "rport" => candidate.rport = Some(v.parse()?),
+                _ => candidate.extensions.push((k, v))
+            }
+        }
+
+        Ok(candidate)
+    }
+}
+
+impl<'a> fmt::Display for CandidateKind<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(format!("{}", CandidateKind::Host), "host");
+    /// assert_eq!(format!("{}", CandidateKind::Srflx), "srflx");
+    /// assert_eq!(format!("{}", CandidateKind::Prflx), "prflx");
+    /// assert_eq!(format!("{}", CandidateKind::Relay), "relay");
+    /// assert_eq!(format!("{}", CandidateKind::Other("panda")), "panda");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Host =>   "host",
+            Self::Srflx =>  "srflx",
+            Self::Prflx =>  "prflx",
+            Self::Relay =>  "relay",
+            Self::Other(kind) => kind
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CandidateKind<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(CandidateKind::try_from("host").unwrap(), CandidateKind::Host);
+    /// assert_eq!(CandidateKind::try_from("srflx").unwrap(), CandidateKind::Srflx);
+    /// assert_eq!(CandidateKind::try_from("prflx").unwrap(), CandidateKind::Prflx);
+    /// assert_eq!(CandidateKind::try_from("relay").unwrap(), CandidateKind::Relay);
+    /// assert_eq!(CandidateKind::try_from("panda").unwrap(), CandidateKind::Other("panda"));
+    /// assert!(CandidateKind::try_from("").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "host" =>   Ok(Self::Host),
+            "srflx" =>  Ok(Self::Srflx),
+            "prflx" =>  Ok(Self::Prflx),
+            "relay" =>  Ok(Self::Relay),
+            "" => Err(anyhow!("invalid candidate type!")),
+            _ => Ok(Self::Other(value))
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use super::{
+    static_payload_type,
+    Attributes,
+    Codec
+};
+
+use std::{
+    borrow::Cow,
+    collections::HashMap
+};
+
+/// codec capability.
+///
+/// a payload type joined with its "a=rtpmap:" and "a=fmtp:" attributes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Capability<'a> {
+    pub pt: u8,
+    pub codec: Codec,
+    pub frequency: Option<u64>,
+    pub channels: Option<u8>,
+    pub params: HashMap<&'a str, Cow<'a, str>>
+}
+
+impl<'a> Capability<'a> {
+    /// whether two capabilities describe the same format.
+    ///
+    /// the encoding name, clock rate and channel count must match, the
+    /// channel count defaults to one when omitted.  For H264 the
+    /// packetization mode must also match, since it changes the payload
+    /// format.  The retransmission format ("rtx") is matched through the
+    /// associated payload type, see `intersect`.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let opus = Capability {
+    ///     pt: 111,
+    ///     codec: Codec::Opus,
+    ///     frequency: Some(48000),
+    ///     channels: Some(2),
+    ///     params: HashMap::new()
+    /// };
+    ///
+    /// let mut other = opus.clone();
+    /// other.pt = 109;
+    /// assert!(opus.is_compatible(&other));
+    ///
+    /// other.channels = None;
+    /// assert!(!opus.is_compatible(&other));
+    /// ```
+    pub fn is_compatible(&self, other: &Capability) -> bool {
+        let packetization = |c: &Capability| c.params
+            .get("packetization-mode")
+            .map(|x| x.to_string())
+            .unwrap_or_else(|| "0".to_string());
+        self.codec == other.codec
+            && self.frequency == other.frequency
+            && self.channels.unwrap_or(1) == other.channels.unwrap_or(1)
+            && (self.codec != Codec::H264 || packetization(self) == packetization(other))
+    }
+
+    /// get the Chrome maximum bitrate, "x-google-max-bitrate", in
+    /// kilobits per second.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("rtpmap:96 VP8/90000").unwrap();
+    /// attributes.handle("fmtp:96 x-google-max-bitrate=2500;x-google-min-bitrate=300;x-google-start-bitrate=1000").unwrap();
+    ///
+    /// let capability = attributes.capability(96).unwrap();
+    /// assert_eq!(capability.max_bitrate(), Some(2500));
+    /// assert_eq!(capability.min_bitrate(), Some(300));
+    /// assert_eq!(capability.start_bitrate(), Some(1000));
+    ///
+    /// attributes.handle("rtpmap:97 VP9/90000").unwrap();
+    /// assert_eq!(attributes.capability(97).unwrap().max_bitrate(), None);
+    /// ```
+    pub fn max_bitrate(&self) -> Option<u32> {
+        self.bitrate("x-google-max-bitrate")
+    }
+
+    /// get the Chrome minimum bitrate, "x-google-min-bitrate", in
+    /// kilobits per second.
+    pub fn min_bitrate(&self) -> Option<u32> {
+        self.bitrate("x-google-min-bitrate")
+    }
+
+    /// get the Chrome start bitrate, "x-google-start-bitrate", in
+    /// kilobits per second.
+    pub fn start_bitrate(&self) -> Option<u32> {
+        self.bitrate("x-google-start-bitrate")
+    }
+
+    fn bitrate(&self, key: &str) -> Option<u32> {
+        self.params.get(key).and_then(|x| x.parse().ok())
+    }
+
+    /// whether the capability has all the given format parameters.
+    pub fn has_params(&self, params: &[(&str, &str)]) -> bool {
+        params.iter().all(|(k, v)| self.params.get(k).map(|x| x.as_ref()) == Some(*v))
+    }
+}
+
+impl<'a> Attributes<'a> {
+    /// get the capability of a payload type.
+    ///
+    /// a static payload type without "a=rtpmap:" resolves to its
+    /// RTP/AVP profile format.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("rtpmap:111 opus/48000/2").unwrap();
+    /// attributes.handle("fmtp:111 minptime=10;useinbandfec=1").unwrap();
+    ///
+    /// let capability = attributes.capability(111).unwrap();
+    /// assert_eq!(capability.codec, Codec::Opus);
+    /// assert_eq!(capability.frequency, Some(48000));
+    /// assert_eq!(capability.channels, Some(2));
+    /// assert_eq!(capability.params["useinbandfec"], "1");
+    /// assert!(attributes.capability(96).is_none());
+    /// assert_eq!(attributes.capability(8).unwrap().codec, Codec::Pcma);
+    /// ```
+    pub fn capability(&self, pt: u8) -> Option<Capability<'a>> {
+        let rtp = self.rtpmap
+            .get(&pt)
+            .cloned()
+            .or_else(|| static_payload_type(pt))?;
+        Some(Capability {
+            pt,
+            codec: rtp.codec,
+            frequency: rtp.frequency,
+            channels: rtp.channels,
+            params: self.fmtp.get(&pt).cloned().unwrap_or_default()
+        })
+    }
+
+    /// find the payload type of a format.
+    ///
+    /// returns the lowest payload type with the codec, clock rate and
+    /// channel count, having all the given format parameters.  `None`
+    /// for the clock rate or the channel count matches any value.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("rtpmap:109 opus/48000/2").unwrap();
+    /// attributes.handle("rtpmap:111 opus/48000/2").unwrap();
+    /// attributes.handle("fmtp:111 minptime=10;useinbandfec=1").unwrap();
+    ///
+    /// let query = [("useinbandfec", "1")];
+    /// assert_eq!(attributes.payload_type(Codec::Opus, Some(48000), Some(2), &query), Some(111));
+    /// assert_eq!(attributes.payload_type(Codec::Opus, None, None, &[]), Some(109));
+    /// assert_eq!(attributes.payload_type(Codec::Opus, Some(16000), None, &[]), None);
+    /// ```
+    pub fn payload_type(
+        &self,
+        codec: Codec,
+        frequency: Option<u64>,
+        channels: Option<u8>,
+        params: &[(&str, &str)]
+    ) -> Option<u8> {
+        let mut pts = self.rtpmap.keys().copied().collect::<Vec<u8>>();
+        pts.sort_unstable();
+        pts.into_iter()
+            .filter_map(|pt| self.capability(pt))
+            .find(|c| {
+                c.codec == codec
+                    && frequency.map(|f| c.frequency == Some(f)).unwrap_or(true)
+                    && channels.map(|n| c.channels.unwrap_or(1) == n).unwrap_or(true)
+                    && c.has_params(params)
+            })
+            .map(|c| c.pt)
+    }
+}
+
+/// intersect two capability sets.
+///
+/// returns the pairs of compatible local and remote payload types,
+/// in the order of the local capabilities, which is the local
+/// preference.  A retransmission payload type is only kept when the
+/// payload type it is associated with ("apt") is kept on both sides.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::attributes::*;
+///
+/// let mut local = Attributes::default();
+/// local.handle("rtpmap:96 VP8/90000").unwrap();
+/// local.handle("rtpmap:97 rtx/90000").unwrap();
+/// local.handle("fmtp:97 apt=96").unwrap();
+/// local.handle("rtpmap:98 H264/90000").unwrap();
+/// local.handle("fmtp:98 packetization-mode=1").unwrap();
+///
+/// let mut remote = Attributes::default();
+/// remote.handle("rtpmap:100 H264/90000").unwrap();
+/// remote.handle("rtpmap:120 VP8/90000").unwrap();
+/// remote.handle("rtpmap:121 rtx/90000").unwrap();
+/// remote.handle("fmtp:121 apt=120").unwrap();
+///
+/// let local = [96, 97, 98].iter().filter_map(|pt| local.capability(*pt)).collect::<Vec<_>>();
+/// let remote = [100, 120, 121].iter().filter_map(|pt| remote.capability(*pt)).collect::<Vec<_>>();
+///
+/// assert_eq!(intersect(&local, &remote), vec![(96, 120), (97, 121)]);
+/// ```
+pub fn intersect(local: &[Capability], remote: &[Capability]) -> Vec<(u8, u8)> {
+    let mut pairs: Vec<(u8, u8)> = Vec::new();
+    for l in local.iter().filter(|c| c.codec != Codec::Rtx) {
+        if let Some(r) = remote.iter().find(|r| l.is_compatible(r)) {
+            pairs.push((l.pt, r.pt));
+        }
+    }
+
+    let apt = |c: &Capability| c.params
+        .get("apt")
+        .and_then(|x| x.parse::<u8>().ok());
+    let mut result = Vec::with_capacity(pairs.len());
+    for l in local {
+        if l.codec != Codec::Rtx {
+            if let Some(pair) = pairs.iter().find(|(pt, _)| *pt == l.pt) {
+                result.push(*pair);
+            }
+
+            continue;
+        }
+
+        let target = apt(l).and_then(|a| pairs.iter().find(|(pt, _)| *pt == a));
+        if let Some((_, remote_apt)) = target {
+            let rtx = remote.iter().find(|r| {
+                r.codec == Codec::Rtx
+                    && r.frequency == l.frequency
+                    && apt(r) == Some(*remote_apt)
+            });
+
+            if let Some(r) = rtx {
+                result.push((l.pt, r.pt));
+            }
+        }
+    }
+
+    result
+}
@@ -8,7 +8,7 @@ use std::{
     fmt
 };
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Codec {
     Vp9,
     Vp8,
@@ -17,7 +17,60 @@ pub enum Codec {
     Av1x,
     Rtx,
     Red,
-    Ulpfec
+    Ulpfec,
+    Opus,
+    Pcmu,
+    Pcma,
+    G722,
+    Cn,
+    TelephoneEvent,
+    Gsm,
+    G723,
+    Dvi4,
+    Lpc,
+    L16,
+    Qcelp,
+    Mpa,
+    G728,
+    G729,
+    CelB,
+    Jpeg,
+    Nv,
+    H261,
+    Mpv,
+    Mp2t,
+    H263
+}
+
+impl Codec {
+    /// get the clock rates allowed by the payload format.
+    ///
+    /// `None` when the clock rate follows the protected or associated
+    /// format, or is not fixed by the payload format.  G722 uses 8000
+    /// for historical reasons although it samples at 16000.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(Codec::Vp8.clock_rates(), Some(&[90000][..]));
+    /// assert_eq!(Codec::Opus.clock_rates(), Some(&[48000][..]));
+    /// assert_eq!(Codec::G722.clock_rates(), Some(&[8000][..]));
+    /// assert_eq!(Codec::Rtx.clock_rates(), None);
+    /// ```
+    pub fn clock_rates(&self) -> Option<&'static [u64]> {
+        match self {
+            Self::Vp9 | Self::Vp8 | Self::H264 | Self::H265 | Self::Av1x => Some(&[90000]),
+            Self::Opus => Some(&[48000]),
+            Self::Pcmu | Self::Pcma | Self::G722 => Some(&[8000]),
+            Self::Gsm | Self::G723 | Self::Lpc | Self::Qcelp | Self::G728 | Self::G729 => Some(&[8000]),
+            Self::Mpa | Self::CelB | Self::Jpeg | Self::Nv => Some(&[90000]),
+            Self::H261 | Self::Mpv | Self::Mp2t | Self::H263 => Some(&[90000]),
+            Self::Rtx | Self::Red | Self::Ulpfec | Self::Cn | Self::TelephoneEvent => None,
+            Self::Dvi4 | Self::L16 => None
+        }
+    }
 }
 
 impl fmt::Display for Codec {
@@ -30,6 +83,8 @@ impl fmt::Display for Codec {
     /// assert_eq!(format!("{}", Codec::Vp8), "VP8");
     /// assert_eq!(format!("{}", Codec::Av1x), "AV1X");
     /// assert_eq!(format!("{}", Codec::H265), "H265");
+    /// assert_eq!(format!("{}", Codec::Opus), "opus");
+    /// assert_eq!(format!("{}", Codec::TelephoneEvent), "telephone-event");
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", match self {
@@ -40,13 +95,37 @@ impl fmt::Display for Codec {
             Self::Av1x =>   "AV1X",
             Self::Rtx =>    "rtx",
             Self::Red =>    "red",
-            Self::Ulpfec => "ulpfec"
+            Self::Ulpfec => "ulpfec",
+            Self::Opus =>   "opus",
+            Self::Pcmu =>   "PCMU",
+            Self::Pcma =>   "PCMA",
+            Self::G722 =>   "G722",
+            Self::Cn =>     "CN",
+            Self::TelephoneEvent => "telephone-event",
+            Self::Gsm =>    "GSM",
+            Self::G723 =>   "G723",
+            Self::Dvi4 =>   "DVI4",
+            Self::Lpc =>    "LPC",
+            Self::L16 =>    "L16",
+            Self::Qcelp =>  "QCELP",
+            Self::Mpa =>    "MPA",
+            Self::G728 =>   "G728",
+            Self::G729 =>   "G729",
+            Self::CelB =>   "CelB",
+            Self::Jpeg =>   "JPEG",
+            Self::Nv =>     "nv",
+            Self::H261 =>   "H261",
+            Self::Mpv =>    "MPV",
+            Self::Mp2t =>   "MP2T",
+            Self::H263 =>   "H263"
         })
     }
 }
 
 impl<'a> TryFrom<&'a str> for Codec {
     type Error = anyhow::Error;
+    /// the encoding names are case-insensitive, see RFC 4855.
+    ///
     /// # Unit Test
     ///
     /// ```
@@ -58,18 +137,47 @@ impl<'a> TryFrom<&'a str> for Codec {
     /// assert_eq!(Codec::try_from("H264").unwrap(), Codec::H264);
     /// assert_eq!(Codec::try_from("H265").unwrap(), Codec::H265);
     /// assert_eq!(Codec::try_from("AV1X").unwrap(), Codec::Av1x);
-    /// assert!(Codec::try_from("av1x").is_err());
+    /// assert_eq!(Codec::try_from("opus").unwrap(), Codec::Opus);
+    /// assert_eq!(Codec::try_from("PCMU").unwrap(), Codec::Pcmu);
+    /// assert_eq!(Codec::try_from("telephone-event").unwrap(), Codec::TelephoneEvent);
+    /// assert_eq!(Codec::try_from("G729").unwrap(), Codec::G729);
+    /// assert_eq!(Codec::try_from("CelB").unwrap(), Codec::CelB);
+    /// assert_eq!(Codec::try_from("av1x").unwrap(), Codec::Av1x);
+    /// assert_eq!(Codec::try_from("OPUS").unwrap(), Codec::Opus);
+    /// assert!(Codec::try_from("foo").is_err());
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        match value {
-            "VP9" =>    Ok(Self::Vp9),
-            "VP8" =>    Ok(Self::Vp8),
-            "H264" =>   Ok(Self::H264),
-            "H265" =>   Ok(Self::H265),
-            "AV1X" =>   Ok(Self::Av1x),
+        match value.to_ascii_lowercase().as_str() {
+            "vp9" =>    Ok(Self::Vp9),
+            "vp8" =>    Ok(Self::Vp8),
+            "h264" =>   Ok(Self::H264),
+            "h265" =>   Ok(Self::H265),
+            "av1x" =>   Ok(Self::Av1x),
             "rtx" =>    Ok(Self::Rtx),
             "red" =>    Ok(Self::Red),
             "ulpfec" => Ok(Self::Ulpfec),
+            "opus" =>   Ok(Self::Opus),
+            "pcmu" =>   Ok(Self::Pcmu),
+            "pcma" =>   Ok(Self::Pcma),
+            "g722" =>   Ok(Self::G722),
+            "cn" =>     Ok(Self::Cn),
+            "telephone-event" => Ok(Self::TelephoneEvent),
+            "gsm" =>    Ok(Self::Gsm),
+            "g723" =>   Ok(Self::G723),
+            "dvi4" =>   Ok(Self::Dvi4),
+            "lpc" =>    Ok(Self::Lpc),
+            "l16" =>    Ok(Self::L16),
+            "qcelp" =>  Ok(Self::Qcelp),
+            "mpa" =>    Ok(Self::Mpa),
+            "g728" =>   Ok(Self::G728),
+            "g729" =>   Ok(Self::G729),
+            "celb" =>   Ok(Self::CelB),
+            "jpeg" =>   Ok(Self::Jpeg),
+            "nv" =>     Ok(Self::Nv),
+            "h261" =>   Ok(Self::H261),
+            "mpv" =>    Ok(Self::Mpv),
+            "mp2t" =>   Ok(Self::Mp2t),
+            "h263" =>   Ok(Self::H263),
             _ => Err(anyhow!("invalid codec!"))
         }
     }
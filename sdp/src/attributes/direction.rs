@@ -0,0 +1,67 @@
+use anyhow::anyhow;
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// media direction.
+///
+/// The direction attributes "a=sendrecv", "a=sendonly", "a=recvonly"
+/// and "a=inactive" are mutually exclusive.  If none of them is present
+/// at media level, the session-level one applies, and if none is present
+/// at session level either, "sendrecv" SHOULD be assumed as the default
+/// for sessions that are not of the conference type "broadcast" or "H332".
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Direction {
+    #[default]
+    SendRecv,
+    SendOnly,
+    RecvOnly,
+    Inactive
+}
+
+impl fmt::Display for Direction {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(format!("{}", Direction::SendRecv), "sendrecv");
+    /// assert_eq!(format!("{}", Direction::SendOnly), "sendonly");
+    /// assert_eq!(format!("{}", Direction::RecvOnly), "recvonly");
+    /// assert_eq!(format!("{}", Direction::Inactive), "inactive");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::SendRecv =>   "sendrecv",
+            Self::SendOnly =>   "sendonly",
+            Self::RecvOnly =>   "recvonly",
+            Self::Inactive =>   "inactive"
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Direction {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(Direction::try_from("sendrecv").unwrap(), Direction::SendRecv);
+    /// assert_eq!(Direction::try_from("sendonly").unwrap(), Direction::SendOnly);
+    /// assert_eq!(Direction::try_from("recvonly").unwrap(), Direction::RecvOnly);
+    /// assert_eq!(Direction::try_from("inactive").unwrap(), Direction::Inactive);
+    /// assert!(Direction::try_from("sendonly ").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "sendrecv" =>   Ok(Self::SendRecv),
+            "sendonly" =>   Ok(Self::SendOnly),
+            "recvonly" =>   Ok(Self::RecvOnly),
+            "inactive" =>   Ok(Self::Inactive),
+            _ => Err(anyhow!("invalid direction!"))
+        }
+    }
+}
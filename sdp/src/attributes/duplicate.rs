@@ -0,0 +1,43 @@
+/// duplicate format policy.
+///
+/// At most one "a=fmtp:" and one "a=rtpmap:" attribute is allowed for
+/// each format, this decides what happens when a description repeats
+/// them for the same payload type.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::attributes::*;
+///
+/// let mut attributes = Attributes::default();
+/// attributes.handle("fmtp:96 profile-level-id=42e01f").unwrap();
+/// attributes.handle("fmtp:96 packetization-mode=1").unwrap();
+/// assert_eq!(attributes.fmtp[&96].len(), 2);
+///
+/// let mut attributes = Attributes::default();
+/// attributes.duplicate = Duplicate::KeepFirst;
+/// attributes.handle("rtpmap:96 VP8/90000").unwrap();
+/// attributes.handle("rtpmap:96 H264/90000").unwrap();
+/// attributes.handle("fmtp:96 max-fr=30").unwrap();
+/// attributes.handle("fmtp:96 max-fs=3600").unwrap();
+/// assert_eq!(attributes.rtpmap[&96].codec, Codec::Vp8);
+/// assert_eq!(attributes.fmtp[&96].len(), 1);
+///
+/// let mut attributes = Attributes::default();
+/// attributes.duplicate = Duplicate::Error;
+/// attributes.handle("rtpmap:96 VP8/90000").unwrap();
+/// attributes.handle("fmtp:96 max-fr=30").unwrap();
+/// assert!(attributes.handle("rtpmap:96 VP8/90000").is_err());
+/// assert!(attributes.handle("fmtp:96 max-fs=3600").is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Duplicate {
+    /// reject the description.
+    Error,
+    /// keep the first attribute and ignore the following ones.
+    KeepFirst,
+    /// merge the format parameters, a repeated parameter or "a=rtpmap:"
+    /// replaces the previous one.
+    #[default]
+    Merge
+}
@@ -0,0 +1,87 @@
+use anyhow::anyhow;
+use std::{
+    str::FromStr,
+    fmt
+};
+
+/// well-known RTP header extension.
+///
+/// the URIs of "a=extmap:" for the header extensions of
+/// [RFC8285](https://datatracker.ietf.org/doc/html/rfc8285) in common
+/// use by WebRTC endpoints.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExtMapUri {
+    /// [RFC6464](https://datatracker.ietf.org/doc/html/rfc6464),
+    /// client-to-mixer audio level.
+    SsrcAudioLevel,
+    /// absolute send time, used by the receiver side bandwidth
+    /// estimation of Chrome.
+    AbsSendTime,
+    /// transport-wide sequence number, for the transport-wide
+    /// congestion control feedback.
+    TransportWideCc,
+    /// [RFC8843](https://datatracker.ietf.org/doc/html/rfc8843),
+    /// the media identification of a BUNDLE group.
+    SdesMid,
+    /// [RFC8852](https://datatracker.ietf.org/doc/html/rfc8852),
+    /// the simulcast stream identifier.
+    SdesRtpStreamId,
+    /// [RFC8852](https://datatracker.ietf.org/doc/html/rfc8852),
+    /// the identifier of the stream repaired by a redundancy stream.
+    SdesRepairedRtpStreamId,
+    /// 3GPP coordination of video orientation.
+    VideoOrientation,
+}
+
+impl fmt::Display for ExtMapUri {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(format!("{}", ExtMapUri::SdesMid), "urn:ietf:params:rtp-hdrext:sdes:mid");
+    /// assert_eq!(format!("{}", ExtMapUri::VideoOrientation), "urn:3gpp:video-orientation");
+    /// ```
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::SsrcAudioLevel =>     "urn:ietf:params:rtp-hdrext:ssrc-audio-level",
+            Self::AbsSendTime =>        "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time",
+            Self::TransportWideCc =>    "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01",
+            Self::SdesMid =>            "urn:ietf:params:rtp-hdrext:sdes:mid",
+            Self::SdesRtpStreamId =>    "urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id",
+            Self::SdesRepairedRtpStreamId => "urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id",
+            Self::VideoOrientation =>   "urn:3gpp:video-orientation",
+        })
+    }
+}
+
+impl FromStr for ExtMapUri {
+    type Err = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let uri: ExtMapUri = "urn:ietf:params:rtp-hdrext:ssrc-audio-level".parse().unwrap();
+    /// assert_eq!(uri, ExtMapUri::SsrcAudioLevel);
+    ///
+    /// let uri: ExtMapUri = "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time".parse().unwrap();
+    /// assert_eq!(uri, ExtMapUri::AbsSendTime);
+    ///
+    /// assert!("urn:ietf:params:rtp-hdrext:toffset".parse::<ExtMapUri>().is_err());
+    /// ```
+    #[rustfmt::skip]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "urn:ietf:params:rtp-hdrext:ssrc-audio-level" =>    Ok(Self::SsrcAudioLevel),
+            "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time" => Ok(Self::AbsSendTime),
+            "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01" => Ok(Self::TransportWideCc),
+            "urn:ietf:params:rtp-hdrext:sdes:mid" =>            Ok(Self::SdesMid),
+            "urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id" =>  Ok(Self::SdesRtpStreamId),
+            "urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id" => Ok(Self::SdesRepairedRtpStreamId),
+            "urn:3gpp:video-orientation" =>                     Ok(Self::VideoOrientation),
+            _ => Err(anyhow!("invalid extmap uri!"))
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{
+    Result,
+    anyhow
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  framesize
+/// Value:  framesize-value
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// framesize-value = payload-type SP width "-" height
+/// width = 1*DIGIT
+/// height = 1*DIGIT
+///
+/// Example:
+/// a=framesize:96 320-240
+///
+/// The frame size of a video payload type, in pixels, from the 3GPP
+/// packet-switched streaming service,
+/// [3GPP TS 26.234](https://www.3gpp.org/ftp/Specs/archive/26_series/26.234/).
+/// It is emitted by mobile clients alongside "a=rtpmap:", the payload
+/// type is the key of `Attributes::framesize`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FrameSize {
+    pub width: u32,
+    pub height: u32
+}
+
+impl fmt::Display for FrameSize {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let size = FrameSize {
+    ///     width: 320,
+    ///     height: 240
+    /// };
+    ///
+    /// assert_eq!(format!("{}", size), "320-240");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.width, self.height)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for FrameSize {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let size: FrameSize = FrameSize::try_from("176-144").unwrap();
+    /// assert_eq!(size.width, 176);
+    /// assert_eq!(size.height, 144);
+    ///
+    /// assert!(FrameSize::try_from("176x144").is_err());
+    /// assert!(FrameSize::try_from("176-").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (width, height) = value
+            .split_once('-')
+            .ok_or_else(|| anyhow!("invalid framesize!"))?;
+        Ok(Self {
+            width: width.parse()?,
+            height: height.parse()?
+        })
+    }
+}
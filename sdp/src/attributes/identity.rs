@@ -0,0 +1,113 @@
+use anyhow::{
+    Result,
+    ensure
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  identity
+/// Value:  identity-assertion
+/// Usage Level:  session
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// identity-attribute  = "identity:" identity-assertion
+///                       [ SP identity-extension
+///                         *(";" [ SP ] identity-extension) ]
+/// identity-assertion  = base64
+/// base64              = 1*(ALPHA / DIGIT / "+" / "/" / "=" )
+/// identity-extension  = extension-att-name [ "=" extension-att-value ]
+/// extension-att-name  = token
+/// extension-att-value = 1*(%x01-09 / %x0b-0c / %x0e-3a / %x3c-ff)
+///                       ; byte-string from [RFC4566] omitting ";"
+///
+/// Example:
+/// a=identity:eyJpZHAiOnsiZG9tYWluIjoiZXhhbXBsZS5vcmcifX0=
+///
+/// The identity assertion of the WebRTC identity architecture,
+/// [RFC8827](https://datatracker.ietf.org/doc/html/rfc8827#section-5.6.4).
+/// The assertion binds the fingerprints of the session description to
+/// an identity, it is carried through unchanged, verifying it is up to
+/// the identity provider.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Identity<'a> {
+    pub assertion: &'a str,
+    pub extensions: Vec<(&'a str, Option<&'a str>)>
+}
+
+impl<'a> fmt::Display for Identity<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let identity = Identity {
+    ///     assertion: "eyJpZHAiOnt9fQ==",
+    ///     extensions: vec![("ext", Some("1")), ("flag", None)]
+    /// };
+    ///
+    /// assert_eq!(format!("{}", identity), "eyJpZHAiOnt9fQ== ext=1;flag");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.assertion)?;
+        for (index, (k, v)) in self.extensions.iter().enumerate() {
+            write!(f, "{}{}", if index == 0 { " " } else { ";" }, k)?;
+            if let Some(v) = v {
+                write!(f, "={}", v)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Identity<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let identity: Identity = Identity::try_from("eyJpZHAiOnt9fQ==").unwrap();
+    /// assert_eq!(identity.assertion, "eyJpZHAiOnt9fQ==");
+    /// assert!(identity.extensions.is_empty());
+    ///
+    /// let identity: Identity = Identity::try_from("eyJpZHAiOnt9fQ== ext=1; flag").unwrap();
+    /// assert_eq!(identity.extensions, vec![("ext", Some("1")), ("flag", None)]);
+    ///
+    /// assert!(Identity::try_from("").is_err());
+    /// assert!(Identity::try_from("not-base64!").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (assertion, extensions) = match value.split_once(' ') {
+            Some((a, e)) => (a, Some(e)),
+            None => (value, None)
+        };
+
+        ensure!(
+            !assertion.is_empty() &&
+            assertion.chars().all(|c| c.is_ascii_alphanumeric() || "+/=".contains(c)),
+            "invalid identity assertion!"
+        );
+
+        let extensions = extensions
+            .into_iter()
+            .flat_map(|e| e.split(';'))
+            .map(|e| e.trim_start())
+            .filter(|e| !e.is_empty())
+            .map(|e| match e.split_once('=') {
+                Some((k, v)) => (k, Some(v)),
+                None => (e, None)
+            })
+            .collect();
+
+        Ok(Self {
+            assertion,
+            extensions
+        })
+    }
+}
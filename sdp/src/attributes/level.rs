@@ -0,0 +1,82 @@
+use super::Key;
+
+/// attribute usage level.
+///
+/// the RFC marks each attribute as usable at session level, media
+/// level or both, a media-level attribute such as "a=ptime:" has no
+/// meaning at session level.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Level {
+    #[default]
+    Session,
+    Media
+}
+
+/// misplaced attribute policy.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Scoping {
+    /// accept the attribute, and keep it in `Attributes::misplaced`.
+    #[default]
+    Warn,
+    /// reject the description.
+    Error
+}
+
+impl Key {
+    /// whether the attribute is allowed at the usage level.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert!(Key::Ptime.is_allowed(Level::Media));
+    /// assert!(!Key::Ptime.is_allowed(Level::Session));
+    /// assert!(Key::Tool.is_allowed(Level::Session));
+    /// assert!(!Key::Tool.is_allowed(Level::Media));
+    /// assert!(Key::SendOnly.is_allowed(Level::Session));
+    /// assert!(Key::SendOnly.is_allowed(Level::Media));
+    /// assert!(Key::GoogleFlag.is_allowed(Level::Session));
+    /// assert!(Key::GoogleFlag.is_allowed(Level::Media));
+    /// ```
+    pub fn is_allowed(&self, level: Level) -> bool {
+        match level {
+            Level::Session => !self.is_media_only(),
+            Level::Media => !self.is_session_only()
+        }
+    }
+
+    fn is_session_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Cat |
+            Self::Tool |
+            Self::Charset |
+            Self::Type |
+            Self::MsidSemantic |
+            Self::Identity
+        )
+    }
+
+    fn is_media_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Ptime |
+            Self::MaxPtime |
+            Self::RtpMap |
+            Self::Fmtp |
+            Self::FrameSize |
+            Self::Orient |
+            Self::Framerate |
+            Self::Quality |
+            Self::Mid |
+            Self::Candidate |
+            Self::Ssrc |
+            Self::SsrcGroup |
+            Self::Msid |
+            Self::RtcpFb |
+            Self::BundleOnly |
+            Self::TlsId
+        )
+    }
+}
@@ -1,16 +1,75 @@
 mod mid;
 mod codec;
+mod candidate;
+mod direction;
+mod refclk;
+mod ssrc;
+mod msid;
+mod capability;
+mod rtcp_fb;
+mod identity;
+mod setup;
+mod framesize;
+mod duplicate;
+mod attribute;
+mod tls_id;
+mod level;
+mod extmap;
+mod static_pt;
 mod kind;
 mod orient;
 mod rtp_value;
 
+pub use candidate::{
+    Candidate,
+    CandidateKind
+};
+
+pub use refclk::{
+    PtpVersion,
+    PtpServer,
+    RefClk,
+    MediaClk
+};
+
+pub use ssrc::{
+    Ssrc,
+    SsrcGroup,
+    Source
+};
+
+pub use capability::{
+    Capability,
+    intersect
+};
+
+pub use direction::Direction;
+pub use rtcp_fb::RtcpFb;
+pub use identity::Identity;
+pub use setup::{
+    TcpConnection,
+    Setup
+};
+pub use msid::{
+    MsidSemantic,
+    Msid
+};
 pub use rtp_value::RtpValue;
+pub use framesize::FrameSize;
+pub use duplicate::Duplicate;
+pub use attribute::Attribute;
+pub use tls_id::TlsId;
+pub use extmap::ExtMapUri;
+pub use static_pt::static_payload_type;
+pub use level::{
+    Scoping,
+    Level
+};
 pub use orient::Orient;
 pub use codec::Codec;
 pub use kind::Kind;
 pub use mid::Mid;
 
-use itertools::Itertools;
 use anyhow::{
     Result,
     ensure,
@@ -18,6 +77,7 @@ use anyhow::{
 };
 
 use std::{
+    borrow::Cow,
     collections::HashMap,
     convert::TryFrom,
     fmt
@@ -37,7 +97,33 @@ pub enum Key {
     Orient,
     Type,
     Framerate,
-    Quality
+    Quality,
+    Mid,
+    Candidate,
+    EndOfCandidates,
+    SendRecv,
+    SendOnly,
+    RecvOnly,
+    Inactive,
+    IceUfrag,
+    IcePwd,
+    TsRefClk,
+    MediaClk,
+    Ssrc,
+    SsrcGroup,
+    Msid,
+    RtcpFb,
+    MsidSemantic,
+    Identity,
+    BundleOnly,
+    Setup,
+    Connection,
+    FrameSize,
+    Cat,
+    Keywds,
+    Tool,
+    TlsId,
+    GoogleFlag
 }
 
 #[derive(Debug, Default)]
@@ -61,7 +147,10 @@ pub struct Attributes<'a> {
     /// not be necessary to know "a=ptime:" to decode RTP or vat audio, and
     /// it is intended as a recommendation for the encoding/packetization of
     /// audio.
-    pub ptime: Option<u64>,
+    ///
+    /// Decimal values such as "20.0" or "2.5" are accepted, see
+    /// `ptime_ms` for the integer milliseconds.
+    pub ptime: Option<f64>,
     /// maxptime (Maximum Packet Time)
     /// 
     /// Name:  maxptime
@@ -85,7 +174,7 @@ pub struct Attributes<'a> {
     /// [RFC2327](https://datatracker.ietf.org/doc/html/rfc2327), 
     /// and implementations that have not been updated will ignore
     /// this attribute.
-    pub maxptime: Option<u64>,
+    pub maxptime: Option<f64>,
     /// Name:  rtpmap
     /// Value:  rtpmap-value
     /// Usage Level:  media
@@ -125,7 +214,18 @@ pub struct Attributes<'a> {
     /// 
     /// The "a=fmtp:" attribute may be used to specify parameters for any
     /// protocol and format that defines use of such parameters.
-    pub fmtp: HashMap<u8, HashMap<&'a str, &'a str>>,
+    ///
+    /// The values borrow the description, a renumbered "apt" value is
+    /// owned, see `MediaDescription::remap_payload_types`.
+    pub fmtp: HashMap<u8, HashMap<&'a str, Cow<'a, str>>>,
+    /// Name:  framesize
+    /// Value:  framesize-value
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=framesize:96 320-240
+    pub framesize: HashMap<u8, FrameSize>,
     /// orient (Orientation)
     /// 
     /// Name:  orient
@@ -144,6 +244,62 @@ pub struct Attributes<'a> {
     /// Example:
     /// a=orient:portrait
     pub orient: Option<Orient>,
+    /// cat (Category)
+    /// 
+    /// Name:  cat
+    /// Value:  cat-value
+    /// Usage Level:  session
+    /// Charset Dependent:  no
+    /// 
+    /// Syntax:
+    /// cat-value = category
+    /// category = non-ws-string
+    /// 
+    /// Example:
+    /// a=cat:foo.bar
+    /// 
+    /// This attribute gives the dot-separated hierarchical category of the
+    /// session.  This is to enable a receiver to filter unwanted sessions by
+    /// category.  There is no central registry of categories.
+    pub cat: Option<&'a str>,
+    /// keywds (Keywords)
+    /// 
+    /// Name:  keywds
+    /// Value:  keywds-value
+    /// Usage Level:  session, media
+    /// Charset Dependent:  yes
+    /// 
+    /// Syntax:
+    /// keywds-value = keywords
+    /// keywords = text
+    /// 
+    /// Example:
+    /// a=keywds:SDP SAP MBONE
+    /// 
+    /// Like the "a=cat:" attribute, this is to assist identifying wanted
+    /// sessions at the receiver.  This allows a receiver to select
+    /// interesting sessions based on keywords describing the purpose of the
+    /// session; there is no central registry of keywords.  Its value should
+    /// be interpreted in the charset specified for the session description
+    /// if one is specified, or by default in ISO 10646/UTF-8.
+    pub keywds: Option<&'a str>,
+    /// tool
+    /// 
+    /// Name:  tool
+    /// Value:  tool-value
+    /// Usage Level:  session
+    /// Charset Dependent:  no
+    /// 
+    /// Syntax:
+    /// tool-value = tool-name-and-version
+    /// tool-name-and-version = text
+    /// 
+    /// Example:
+    /// a=tool:foobar V3.2
+    /// 
+    /// This gives the name and version number of the tool used to create
+    /// the session description.
+    pub tool: Option<&'a str>,
     /// Name:  charset
     /// Value:  charset-value
     /// Usage Level:  session
@@ -333,6 +489,18 @@ pub struct Attributes<'a> {
     /// receive-only mode MUST still send RTCP packets as described in
     /// [RFC3550](https://datatracker.ietf.org/doc/html/rfc3550#section-6).
     pub recvonly: bool,
+    /// Name:  sendrecv
+    /// Value:
+    /// Usage Level:  session, media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=sendrecv
+    /// 
+    /// This specifies that the tools should be started in send and receive
+    /// mode.  This is necessary for interactive multimedia conferences with
+    /// tools that default to receive-only mode.
+    pub sendrecv: bool,
     /// Name:  sendonly
     /// Value:
     /// Usage Level:  session, media
@@ -348,20 +516,6 @@ pub struct Attributes<'a> {
     /// receive-vonly mode.  Note that send-only mode applies only to the
     /// media, and any associated control protocol (e.g., RTCP) SHOULD still
     /// be received and processed as normal.
-    pub sendrecv: bool,
-    /// Name:  inactive
-    /// Value:
-    /// Usage Level:  session, media
-    /// Charset Dependent:  no
-    /// 
-    /// Example:
-    /// a=inactive
-    /// 
-    /// This specifies that the tools should be started in inactive mode.
-    /// This is necessary for interactive multimedia conferences where users
-    /// can put other users on hold.  No media is sent over an inactive media
-    /// stream.  Note that an RTP-based system MUST still send RTCP (if RTCP
-    /// is used), even if started in inactive mode.
     pub sendonly: bool,
     /// Name:  inactive
     /// Value:
@@ -381,7 +535,203 @@ pub struct Attributes<'a> {
     pub extmap: HashMap<u8, &'a str>,
     
     pub mid: Option<Mid>,
-    
+    /// Name:  candidate
+    /// Value:  candidate-value
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=candidate:1 1 UDP 2130706431 203.0.113.141 8998 typ host
+    /// 
+    /// The candidates gathered for the media, in the order in which 
+    /// they were received.  With trickle ICE, candidates keep being 
+    /// added to an already negotiated media description until the 
+    /// gathering is finished.
+    pub candidates: Vec<Candidate<'a>>,
+    /// Name:  end-of-candidates
+    /// Value:
+    /// Usage Level:  session, media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=end-of-candidates
+    /// 
+    /// This attribute indicates that the agent has finished gathering
+    /// candidates for the current ICE generation, no further candidates
+    /// will be trickled for it.
+    pub end_of_candidates: bool,
+    /// The attributes that are not recognized by this library.
+    ///
+    /// An SDP parser MUST ignore any attribute it doesn't understand,
+    /// but they are kept here verbatim (the text after "a=") and in
+    /// the order of appearance, so that vendor-specific attributes
+    /// survive re-serialization.
+    pub unknown: Vec<&'a str>,
+    /// All the accepted attributes verbatim (the text after "a="), in
+    /// the order of appearance, including the trickled candidates, see
+    /// `iter`.  These are the lines written by `Writer::attributes`,
+    /// the methods changing the fields update them too.
+    pub lines: Vec<Cow<'a, str>>,
+    /// Name:  ice-ufrag
+    /// Value:  ufrag
+    /// Usage Level:  session, media
+    /// Charset Dependent:  no
+    /// 
+    /// Syntax:
+    /// ice-ufrag-att = "ice-ufrag:" ufrag
+    /// ufrag = 4*256ice-char
+    /// 
+    /// Example:
+    /// a=ice-ufrag:8hhY
+    /// 
+    /// The "ice-pwd" and "ice-ufrag" attributes can appear at either the
+    /// session-level or media-level.  When present in both, the value in
+    /// the media-level takes precedence.  Thus, the value at the session-
+    /// level is effectively a default that applies to all data streams,
+    /// unless overridden by a media-level value.
+    pub ice_ufrag: Option<&'a str>,
+    /// Name:  ice-pwd
+    /// Value:  password
+    /// Usage Level:  session, media
+    /// Charset Dependent:  no
+    /// 
+    /// Syntax:
+    /// ice-pwd-att = "ice-pwd:" password
+    /// password = 22*256ice-char
+    /// 
+    /// Example:
+    /// a=ice-pwd:asd88fgpdd777uzjYhagZg
+    pub ice_pwd: Option<&'a str>,
+    /// Name:  ts-refclk
+    /// Value:  clksrc
+    /// Usage Level:  session, media, source
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=ts-refclk:ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:0
+    /// 
+    /// The timestamp reference clocks, in the order of appearance.
+    pub ts_refclk: Vec<RefClk<'a>>,
+    /// Name:  mediaclk
+    /// Value:  mediaclock
+    /// Usage Level:  session, media, source
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=mediaclk:direct=0
+    pub mediaclk: Option<MediaClk<'a>>,
+    /// Name:  ssrc
+    /// Value:  ssrc-attr
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=ssrc:314159 cname:user@example.com
+    /// 
+    /// The source-level attributes, in the order of appearance.
+    pub ssrcs: Vec<Ssrc<'a>>,
+    /// Name:  ssrc-group
+    /// Value:  ssrc-group-attr
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=ssrc-group:FID 314159 271828
+    pub ssrc_groups: Vec<SsrcGroup<'a>>,
+    /// Name:  msid
+    /// Value:  msid-value
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=msid:stream track
+    /// 
+    /// Multiple "a=msid:" attributes can be present when the track 
+    /// belongs to several media streams.
+    pub msid: Vec<Msid<'a>>,
+    /// Name:  rtcp-fb
+    /// Value:  rtcp-fb-val
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=rtcp-fb:96 nack pli
+    pub rtcp_fb: Vec<RtcpFb<'a>>,
+    /// Name:  msid-semantic
+    /// Value:  msid-semantic-value
+    /// Usage Level:  session
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=msid-semantic: WMS stream
+    pub msid_semantic: Option<MsidSemantic<'a>>,
+    /// Name:  identity
+    /// Value:  identity-assertion
+    /// Usage Level:  session
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=identity:eyJpZHAiOnsiZG9tYWluIjoiZXhhbXBsZS5vcmcifX0=
+    pub identity: Option<Identity<'a>>,
+    /// Name:  bundle-only
+    /// Value:  N/A
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=bundle-only
+    /// 
+    /// The "bundle-only" attribute indicates that the media section is
+    /// only to be used if it is part of a BUNDLE group.  An offerer assigns
+    /// a zero port value to such a bundled "m=" section, an answerer MUST
+    /// NOT include the attribute in the answer, and MUST reject the media
+    /// section by assigning a zero port if it does not accept it into the
+    /// BUNDLE group.
+    pub bundle_only: bool,
+    /// Name:  setup
+    /// Value:  role
+    /// Usage Level:  session, media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=setup:actpass
+    pub setup: Option<Setup>,
+    /// Name:  connection
+    /// Value:  conn-value
+    /// Usage Level:  session, media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=connection:existing
+    pub connection: Option<TcpConnection>,
+    /// Name:  tls-id
+    /// Value:  tls-id-value
+    /// Usage Level:  media
+    /// Charset Dependent:  no
+    /// 
+    /// Example:
+    /// a=tls-id:abc3de65cddef001be82
+    pub tls_id: Option<TlsId<'a>>,
+    /// Name:  x-google-flag
+    /// Usage Level:  session, media
+    /// 
+    /// Example:
+    /// a=x-google-flag:conference
+    /// 
+    /// A non-standard attribute of Chrome, "conference" enables the
+    /// simulcast screen sharing of the conference mode, Chrome puts
+    /// it in the video media section.
+    pub google_flag: Option<&'a str>,
+    /// the policy for repeated "a=fmtp:" and "a=rtpmap:" attributes
+    /// of the same format, merged by default.
+    pub duplicate: Duplicate,
+    /// the usage level of this scope, session by default.
+    pub level: Level,
+    /// the policy for attributes not allowed at this usage level.
+    pub scoping: Scoping,
+    /// The attributes not allowed at this usage level, verbatim (the
+    /// text after "a="), in the order of appearance.
+    pub misplaced: Vec<&'a str>,
 }
 
 impl<'a> Attributes<'a> {
@@ -397,62 +747,462 @@ impl<'a> Attributes<'a> {
     /// assert_eq!(value.codec, Codec::Vp8);
     /// assert_eq!(value.frequency, Some(9000));
     /// assert_eq!(value.channels, None);
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("x-google-flag:conference").unwrap();
+    /// attributes.handle("ptime:20").unwrap();
+    /// attributes.handle("rtcp-mux").unwrap();
+    /// attributes.handle("extmap-allow-mixed").unwrap();
+    ///
+    /// attributes.handle("ts-refclk:ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:0").unwrap();
+    /// attributes.handle("mediaclk:direct=0").unwrap();
+    ///
+    /// attributes.handle("fmtp:101 0-15").unwrap();
+    /// attributes.handle("fmtp:96 profile-level-id=42e01f; sprop-parameter-sets=Z0IACpZTBYmI,aMljiA==").unwrap();
+    ///
+    /// assert_eq!(attributes.fmtp[&101]["0-15"], "");
+    /// assert_eq!(attributes.fmtp[&96]["profile-level-id"], "42e01f");
+    /// assert_eq!(attributes.fmtp[&96]["sprop-parameter-sets"], "Z0IACpZTBYmI,aMljiA==");
+    /// attributes.handle("msid-semantic: WMS stream").unwrap();
+    ///
+    /// attributes.handle("tool:foobar V3.2").unwrap();
+    /// attributes.handle("cat:foo.bar").unwrap();
+    /// attributes.handle("keywds:SDP SAP MBONE").unwrap();
+    ///
+    /// assert_eq!(attributes.tool, Some("foobar V3.2"));
+    /// assert_eq!(attributes.cat, Some("foo.bar"));
+    /// assert_eq!(attributes.keywds, Some("SDP SAP MBONE"));
+    ///
+    /// attributes.handle("framesize:96 320-240").unwrap();
+    /// assert_eq!(attributes.framesize[&96], FrameSize { width: 320, height: 240 });
+    ///
+    /// attributes.handle("setup:actpass").unwrap();
+    /// attributes.handle("connection:new").unwrap();
+    /// attributes.handle("tls-id:abc3de65cddef001be82").unwrap();
+    ///
+    /// assert_eq!(attributes.setup, Some(Setup::ActPass));
+    /// assert_eq!(attributes.connection, Some(TcpConnection::New));
+    /// assert_eq!(attributes.tls_id, Some(TlsId("abc3de65cddef001be82")));
+    /// assert_eq!(attributes.msid_semantic.as_ref().unwrap().ids, vec!["stream"]);
+    /// assert_eq!(attributes.ptime, Some(20.0));
+    /// assert_eq!(attributes.ts_refclk.len(), 1);
+    /// assert_eq!(attributes.mediaclk, Some(MediaClk::Direct { offset: Some(0), rate: None }));
+    /// assert_eq!(attributes.google_flag, Some("conference"));
+    /// assert_eq!(attributes.unknown, vec![
+    ///     "rtcp-mux",
+    ///     "extmap-allow-mixed"
+    /// ]);
     /// ```
     pub fn handle(&mut self, line: &'a str) -> Result<()> {
-        let values = line.split(':').collect::<Vec<&str>>();
-        ensure!(!values.is_empty(), "invalid attributes!");
-        let key = match Key::try_from(values[0]) {
+        if self.handle_line(line)? {
+            self.lines.push(Cow::Borrowed(line));
+        }
+
+        Ok(())
+    }
+
+    /// return false when the line is ignored, see `Duplicate::KeepFirst`.
+    fn handle_line(&mut self, line: &'a str) -> Result<bool> {
+        let Attribute { name, value } = Attribute::from(line);
+        let key = match Key::try_from(name) {
             Ok(k) => k,
-            _ => return Ok(())
+            _ => {
+                self.unknown.push(line);
+                return Ok(true)
+            }
         };
-        
-        Ok(match key {
-            Key::Fmtp      => self.handle_fmtp(values[1])?,
-            Key::Lang      => self.lang = Some(values[1]),
-            Key::RtpMap    => self.handle_rtpmap(values[1])?,
-            Key::ExtMap    => self.handle_extmap(values[1])?,
-            Key::Charset   => self.charset = Some(values[1]),
-            Key::SdpLang   => self.sdplang = Some(values[1]),
-            Key::Ptime     => self.ptime = Some(values[1].parse()?),
-            Key::MaxPtime  => self.maxptime = Some(values[1].parse()?),
-            Key::Orient    => self.orient = Some(Orient::try_from(values[1])?),
-            Key::Type      => self.kind = Some(Kind::try_from(values[1])?),
-            Key::Framerate => self.framerate = Some(values[1].parse()?),
-            Key::Quality   => self.quality = Some(values[1].parse()?),
-        })
+
+        if !key.is_allowed(self.level) {
+            match self.scoping {
+                Scoping::Error => return Err(anyhow!("misplaced attribute!")),
+                Scoping::Warn => self.misplaced.push(line)
+            }
+        }
+
+        match key {
+            Key::EndOfCandidates => self.end_of_candidates = true,
+            Key::SendRecv => self.sendrecv = true,
+            Key::SendOnly => self.sendonly = true,
+            Key::RecvOnly => self.recvonly = true,
+            Key::Inactive => self.inactive = true,
+            Key::BundleOnly => self.bundle_only = true,
+            _ => ()
+        }
+
+        if key.is_flag() {
+            return Ok(true)
+        }
+
+        let value = value.ok_or_else(|| anyhow!("invalid attributes!"))?;
+        match key {
+            Key::Fmtp      => return self.handle_fmtp(value),
+            Key::Lang      => self.lang = Some(value),
+            Key::RtpMap    => return self.handle_rtpmap(value),
+            Key::FrameSize => self.handle_framesize(value)?,
+            Key::ExtMap    => self.handle_extmap(value)?,
+            Key::Charset   => self.charset = Some(value),
+            Key::Cat       => self.cat = Some(value),
+            Key::Keywds    => self.keywds = Some(value),
+            Key::Tool      => self.tool = Some(value),
+            Key::SdpLang   => self.sdplang = Some(value),
+            Key::Ptime     => self.ptime = Some(packet_time(value)?),
+            Key::MaxPtime  => self.maxptime = Some(packet_time(value)?),
+            Key::Orient    => self.orient = Some(Orient::try_from(value)?),
+            Key::Type      => self.kind = Some(Kind::try_from(value)?),
+            Key::Framerate => self.framerate = Some(value.parse()?),
+            Key::Quality   => self.quality = Some(value.parse()?),
+            Key::Mid       => self.mid = Some(Mid::try_from(value)?),
+            Key::Candidate => self.candidates.push(Candidate::try_from(value)?),
+            Key::IceUfrag  => self.ice_ufrag = Some(value),
+            Key::IcePwd    => self.ice_pwd = Some(value),
+            Key::TsRefClk  => self.ts_refclk.push(RefClk::try_from(value)?),
+            Key::MediaClk  => self.mediaclk = Some(MediaClk::try_from(value)?),
+            Key::Ssrc      => self.ssrcs.push(Ssrc::try_from(value)?),
+            Key::SsrcGroup => self.ssrc_groups.push(SsrcGroup::try_from(value)?),
+            Key::Msid      => self.msid.push(Msid::try_from(value)?),
+            Key::RtcpFb    => self.rtcp_fb.push(RtcpFb::try_from(value)?),
+            Key::MsidSemantic => self.msid_semantic = Some(MsidSemantic::try_from(value)?),
+            Key::Identity  => self.identity = Some(Identity::try_from(value)?),
+            Key::Setup     => self.setup = Some(Setup::try_from(value)?),
+            Key::Connection => self.connection = Some(TcpConnection::try_from(value)?),
+            Key::TlsId     => self.tls_id = Some(TlsId::try_from(value)?),
+            Key::GoogleFlag => self.google_flag = Some(value),
+            _ => ()
+        }
+
+        Ok(true)
+    }
+
+    /// iterate the attributes in the order of appearance.
+    ///
+    /// the fields keep the value of each attribute, the hash map ones
+    /// lose their order, which matters for byte-exact re-serialization,
+    /// so the writer writes these lines.  the lines ignored by the
+    /// `duplicate` policy are not kept.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("rtpmap:111 opus/48000/2").unwrap();
+    /// attributes.handle("rtcp-mux").unwrap();
+    /// attributes.handle("rtpmap:0 PCMU/8000").unwrap();
+    ///
+    /// let names = attributes.iter().map(|a| a.name).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["rtpmap", "rtcp-mux", "rtpmap"]);
+    /// assert_eq!(attributes.rtpmap_order(), vec![111, 0]);
+    ///
+    /// // a rejected or ignored line is not kept.
+    /// attributes.duplicate = Duplicate::KeepFirst;
+    /// attributes.handle("rtpmap:0 PCMA/8000").unwrap();
+    /// assert_eq!(attributes.rtpmap[&0].codec, Codec::Pcmu);
+    /// attributes.duplicate = Duplicate::Error;
+    /// assert!(attributes.handle("rtpmap:0 PCMA/8000").is_err());
+    /// assert!(attributes.handle("ptime:0").is_err());
+    /// assert_eq!(attributes.iter().count(), 3);
+    ///
+    /// // a merged "a=rtpmap:" replaces the previous line.
+    /// attributes.duplicate = Duplicate::Merge;
+    /// attributes.handle("rtpmap:111 opus/48000/2").unwrap();
+    /// assert_eq!(attributes.rtpmap_order(), vec![0, 111]);
+    /// assert_eq!(attributes.iter().count(), 3);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Attribute<'_>> + '_ {
+        self.lines.iter().map(|line| Attribute::from(line.as_ref()))
+    }
+
+    /// get the payload types of "a=rtpmap:" in the order of appearance.
+    ///
+    /// payload types removed from `rtpmap` since parsing are skipped.
+    pub fn rtpmap_order(&self) -> Vec<u8> {
+        let mut pts = Vec::with_capacity(self.rtpmap.len());
+        let values = self.iter()
+            .filter(|a| a.key() == Some(Key::RtpMap))
+            .filter_map(|a| a.value)
+            .filter_map(|v| v.split_once(' '))
+            .filter_map(|(pt, _)| pt.parse::<u8>().ok());
+        for pt in values {
+            if self.rtpmap.contains_key(&pt) && !pts.contains(&pt) {
+                pts.push(pt);
+            }
+        }
+
+        pts
+    }
+
+    /// whether the Chrome conference mode is enabled.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.level = Level::Media;
+    /// attributes.scoping = Scoping::Error;
+    /// assert!(!attributes.is_conference());
+    ///
+    /// attributes.handle("x-google-flag:conference").unwrap();
+    /// assert!(attributes.is_conference());
+    /// assert!(attributes.misplaced.is_empty());
+    /// ```
+    pub fn is_conference(&self) -> bool {
+        self.google_flag == Some("conference")
+    }
+
+    /// get the header extension id of a well-known URI.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid").unwrap();
+    /// attributes.handle("extmap:5 urn:ietf:params:rtp-hdrext:toffset").unwrap();
+    ///
+    /// assert_eq!(attributes.extmap_id(ExtMapUri::SdesMid), Some(4));
+    /// assert_eq!(attributes.extmap_id(ExtMapUri::AbsSendTime), None);
+    /// assert_eq!(attributes.extmap_uri(4), Some(ExtMapUri::SdesMid));
+    /// assert_eq!(attributes.extmap_uri(5), None);
+    /// ```
+    pub fn extmap_id(&self, uri: ExtMapUri) -> Option<u8> {
+        self.extmap
+            .iter()
+            .find(|(_, v)| v.parse::<ExtMapUri>().ok() == Some(uri))
+            .map(|(id, _)| *id)
+    }
+
+    /// get the well-known URI of a header extension id.
+    pub fn extmap_uri(&self, id: u8) -> Option<ExtMapUri> {
+        self.extmap.get(&id).and_then(|v| v.parse().ok())
+    }
+
+    /// get the packet time in milliseconds.
+    ///
+    /// decimal values are rounded to the nearest millisecond.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// assert_eq!(attributes.ptime_ms(), None);
+    ///
+    /// attributes.handle("ptime:20.0").unwrap();
+    /// attributes.handle("maxptime:120").unwrap();
+    /// assert_eq!(attributes.ptime, Some(20.0));
+    /// assert_eq!(attributes.ptime_ms(), Some(20));
+    /// assert_eq!(attributes.maxptime_ms(), Some(120));
+    ///
+    /// attributes.handle("ptime:2.5").unwrap();
+    /// assert_eq!(attributes.ptime_ms(), Some(3));
+    ///
+    /// assert!(attributes.handle("ptime:0").is_err());
+    /// assert!(attributes.handle("ptime:-20").is_err());
+    /// assert!(attributes.handle("maxptime:NaN").is_err());
+    /// ```
+    pub fn ptime_ms(&self) -> Option<u64> {
+        self.ptime.map(|x| x.round() as u64)
+    }
+
+    /// get the maximum packet time in milliseconds.
+    ///
+    /// decimal values are rounded to the nearest millisecond.
+    pub fn maxptime_ms(&self) -> Option<u64> {
+        self.maxptime.map(|x| x.round() as u64)
+    }
+
+    /// get media direction.
+    ///
+    /// returns the direction attribute present in this scope, if any.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// assert_eq!(attributes.direction(), None);
+    ///
+    /// attributes.handle("recvonly").unwrap();
+    /// assert_eq!(attributes.direction(), Some(Direction::RecvOnly));
+    /// ```
+    pub fn direction(&self) -> Option<Direction> {
+        if self.sendrecv {
+            Some(Direction::SendRecv)
+        } else if self.sendonly {
+            Some(Direction::SendOnly)
+        } else if self.recvonly {
+            Some(Direction::RecvOnly)
+        } else if self.inactive {
+            Some(Direction::Inactive)
+        } else {
+            None
+        }
+    }
+
+    /// add a trickled candidate.
+    ///
+    /// append the candidate to an already parsed media description
+    /// and return the corresponding "a=candidate:" line fragment,
+    /// ready to be sent to the remote peer over signaling.  once 
+    /// "a=end-of-candidates" has been seen no more candidates are 
+    /// accepted for the current generation.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// let candidate = Candidate::try_from(
+    ///     "1 1 UDP 2130706431 203.0.113.141 8998 typ host"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(
+    ///     attributes.add_candidate(candidate).unwrap(),
+    ///     "a=candidate:1 1 UDP 2130706431 203.0.113.141 8998 typ host\r\n"
+    /// );
+    /// 
+    /// assert_eq!(attributes.candidates.len(), 1);
+    /// assert_eq!(attributes.end_candidates(), "a=end-of-candidates\r\n");
+    /// assert!(attributes.end_of_candidates);
+    ///
+    /// let names = attributes.iter().map(|a| a.name).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["candidate", "end-of-candidates"]);
+    ///
+    /// let candidate = Candidate::try_from(
+    ///     "2 1 UDP 1694498815 192.0.2.33 10000 typ srflx raddr 10.0.1.1 rport 8998"
+    /// ).unwrap();
+    ///
+    /// assert!(attributes.add_candidate(candidate).is_err());
+    /// ```
+    pub fn add_candidate(&mut self, candidate: Candidate<'a>) -> Result<String> {
+        ensure!(!self.end_of_candidates, "candidates is ended!");
+        let line = format!("candidate:{}", candidate);
+        let fragment = format!("a={}\r\n", line);
+        self.lines.push(Cow::Owned(line));
+        self.candidates.push(candidate);
+        Ok(fragment)
+    }
+
+    /// mark the end of candidates.
+    ///
+    /// return the "a=end-of-candidates" line fragment to be sent 
+    /// to the remote peer, indicating that gathering is complete.
+    pub fn end_candidates(&mut self) -> String {
+        if !self.end_of_candidates {
+            self.lines.push(Cow::Borrowed("end-of-candidates"));
+        }
+
+        self.end_of_candidates = true;
+        "a=end-of-candidates\r\n".to_string()
+    }
+    
+    /// a repeated "a=rtpmap:" follows the `duplicate` policy, when
+    /// merged, its line replaces the previous one.
+    fn handle_rtpmap(&mut self, value: &str) -> Result<bool> {
+        let (pt, rtp) = value
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid rtpmap!"))?;
+        let pt: u8 = pt.parse()?;
+        let rtp = RtpValue::try_from(rtp)?;
+        if self.rtpmap.contains_key(&pt) {
+            match self.duplicate {
+                Duplicate::Error => return Err(anyhow!("duplicate rtpmap!")),
+                Duplicate::KeepFirst => return Ok(false),
+                Duplicate::Merge => self.lines.retain(|line| {
+                    let attribute = Attribute::from(line.as_ref());
+                    attribute.key() != Some(Key::RtpMap) || attribute.format() != Some(pt)
+                })
+            }
+        }
+
+        self.rtpmap.insert(pt, rtp);
+        Ok(true)
     }
     
-    fn handle_rtpmap(&mut self, value: &str) -> Result<()> {
-        let values = value.split(' ').collect::<Vec<&str>>();
-        ensure!(values.len() == 2, "invalid rtpmap!");
-        let rtp = RtpValue::try_from(values[1])?;
-        self.rtpmap.insert(values[0].parse()?, rtp);
+    fn handle_framesize(&mut self, value: &str) -> Result<()> {
+        let (pt, size) = value
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid framesize!"))?;
+        self.framesize.insert(pt.parse()?, FrameSize::try_from(size)?);
         Ok(())
     }
     
     fn handle_extmap(&mut self, value: &'a str) -> Result<()> {
-        let values = value.split(' ').collect::<Vec<&str>>();
-        ensure!(values.len() == 2, "invalid extmap!");
-        self.extmap.insert(values[0].parse()?, values[1]);
+        let (id, uri) = value
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid extmap!"))?;
+        ensure!(!uri.contains(' '), "invalid extmap!");
+        self.extmap.insert(id.parse()?, uri);
         Ok(())
     }
     
-    fn handle_fmtp(&mut self, value: &'a str) -> Result<()> {
-        let values = value.split(' ').collect::<Vec<&str>>();
-        ensure!(values.len() == 2, "invalid fmtp!");
-        let key: u8 = values[0].parse()?;
-        values[1]
+    /// format parameters without value, such as "0-15" for
+    /// telephone-event or "CIF" for H263, are kept with an empty
+    /// value.
+    ///
+    /// repeated formats follow the `duplicate` policy.
+    fn handle_fmtp(&mut self, value: &'a str) -> Result<bool> {
+        let (key, params) = value
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid fmtp!"))?;
+        let key: u8 = key.parse()?;
+        if self.fmtp.contains_key(&key) {
+            match self.duplicate {
+                Duplicate::Error => return Err(anyhow!("duplicate fmtp!")),
+                Duplicate::KeepFirst => return Ok(false),
+                Duplicate::Merge => ()
+            }
+        }
+
+        let fmtp = self.fmtp
+            .entry(key)
+            .or_insert_with(|| HashMap::with_capacity(10));
+        params
             .split(';')
-            .map(|x| x.split('=').collect_tuple::<(&'a str, &'a str)>())
-            .filter(|x| x.is_some())
-            .for_each(|option| {
-                let (k, v) = option.unwrap();
-                self.fmtp
-                    .entry(key)
-                    .or_insert_with(|| HashMap::with_capacity(10))
-                    .insert(k, v);
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .map(|x| x.split_once('=').unwrap_or((x, "")))
+            .for_each(|(k, v)| {
+                fmtp.insert(k, Cow::Borrowed(v));
             });
-        Ok(())
+        Ok(true)
+    }
+}
+
+impl Key {
+    /// whether the attribute is a property attribute.
+    ///
+    /// a property attribute has the form "a=<attribute-name>" and 
+    /// carries no value, it is a binary attribute.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::Key;
+    ///
+    /// assert!(Key::SendRecv.is_flag());
+    /// assert!(Key::EndOfCandidates.is_flag());
+    /// assert!(Key::BundleOnly.is_flag());
+    /// assert!(!Key::Mid.is_flag());
+    /// ```
+    pub fn is_flag(&self) -> bool {
+        matches!(
+            self, 
+            Self::EndOfCandidates |
+            Self::SendRecv |
+            Self::SendOnly |
+            Self::RecvOnly |
+            Self::Inactive |
+            Self::BundleOnly
+        )
     }
 }
 
@@ -481,6 +1231,32 @@ impl fmt::Display for Key {
             Self::Type      => "type",
             Self::Framerate => "framerate",
             Self::Quality   => "quality",
+            Self::Mid       => "mid",
+            Self::Candidate => "candidate",
+            Self::EndOfCandidates => "end-of-candidates",
+            Self::SendRecv  => "sendrecv",
+            Self::SendOnly  => "sendonly",
+            Self::RecvOnly  => "recvonly",
+            Self::Inactive  => "inactive",
+            Self::IceUfrag  => "ice-ufrag",
+            Self::IcePwd    => "ice-pwd",
+            Self::TsRefClk  => "ts-refclk",
+            Self::MediaClk  => "mediaclk",
+            Self::Ssrc      => "ssrc",
+            Self::SsrcGroup => "ssrc-group",
+            Self::Msid      => "msid",
+            Self::RtcpFb    => "rtcp-fb",
+            Self::MsidSemantic => "msid-semantic",
+            Self::Identity  => "identity",
+            Self::BundleOnly => "bundle-only",
+            Self::Setup     => "setup",
+            Self::Connection => "connection",
+            Self::FrameSize => "framesize",
+            Self::Cat       => "cat",
+            Self::Keywds    => "keywds",
+            Self::Tool      => "tool",
+            Self::TlsId     => "tls-id",
+            Self::GoogleFlag => "x-google-flag",
         })
     }
 }
@@ -517,7 +1293,40 @@ impl<'a> TryFrom<&'a str> for Key {
             "type"      => Ok(Self::Type),
             "framerate" => Ok(Self::Framerate),
             "quality"   => Ok(Self::Quality),
+            "mid"       => Ok(Self::Mid),
+            "candidate" => Ok(Self::Candidate),
+            "end-of-candidates" => Ok(Self::EndOfCandidates),
+            "sendrecv"  => Ok(Self::SendRecv),
+            "sendonly"  => Ok(Self::SendOnly),
+            "recvonly"  => Ok(Self::RecvOnly),
+            "inactive"  => Ok(Self::Inactive),
+            "ice-ufrag" => Ok(Self::IceUfrag),
+            "ice-pwd"   => Ok(Self::IcePwd),
+            "ts-refclk" => Ok(Self::TsRefClk),
+            "mediaclk"  => Ok(Self::MediaClk),
+            "ssrc"      => Ok(Self::Ssrc),
+            "ssrc-group" => Ok(Self::SsrcGroup),
+            "msid"      => Ok(Self::Msid),
+            "rtcp-fb"   => Ok(Self::RtcpFb),
+            "msid-semantic" => Ok(Self::MsidSemantic),
+            "identity"  => Ok(Self::Identity),
+            "bundle-only" => Ok(Self::BundleOnly),
+            "setup"     => Ok(Self::Setup),
+            "connection" => Ok(Self::Connection),
+            "framesize" => Ok(Self::FrameSize),
+            "cat"       => Ok(Self::Cat),
+            "keywds"    => Ok(Self::Keywds),
+            "tool"      => Ok(Self::Tool),
+            "tls-id"    => Ok(Self::TlsId),
+            "x-google-flag" => Ok(Self::GoogleFlag),
             _ => Err(anyhow!("invalid sdp attributes keys!"))
         }
     }
 }
+
+/// non-zero-int-or-real
+fn packet_time(value: &str) -> Result<f64> {
+    let time: f64 = value.parse()?;
+    ensure!(time.is_finite() && time > 0.0, "invalid packet time!");
+    Ok(time)
+}
@@ -0,0 +1,168 @@
+use anyhow::{
+    Result,
+    ensure,
+    anyhow
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  msid
+/// Value:  msid-value
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// msid-value = msid-id [ SP msid-appdata ]
+/// msid-id = 1*64token-char ; see RFC 4566
+/// msid-appdata = 1*64token-char  ; see RFC 4566
+///
+/// Example:
+/// a=msid:61317484-2ed4-49d7-9eb7-1414322a7aae f30bdb4a-5db8-49b5-bcdc-e0c9a23172e0
+///
+/// The identifier of the media stream the media description belongs
+/// to, followed by the identifier of the track within the stream,
+/// [RFC8830](https://datatracker.ietf.org/doc/html/rfc8830).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Msid<'a> {
+    pub id: &'a str,
+    pub appdata: Option<&'a str>
+}
+
+impl<'a> fmt::Display for Msid<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let msid = Msid {
+    ///     id: "stream",
+    ///     appdata: Some("track")
+    /// };
+    ///
+    /// assert_eq!(format!("{}", msid), "stream track");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if let Some(appdata) = self.appdata {
+            write!(f, " {}", appdata)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Msid<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let msid: Msid = Msid::try_from("stream track").unwrap();
+    /// assert_eq!(msid.id, "stream");
+    /// assert_eq!(msid.appdata, Some("track"));
+    ///
+    /// let msid: Msid = Msid::try_from("-").unwrap();
+    /// assert_eq!(msid.id, "-");
+    /// assert_eq!(msid.appdata, None);
+    ///
+    /// assert!(Msid::try_from("").is_err());
+    /// assert!(Msid::try_from("stream track extra").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (id, appdata) = match value.split_once(' ') {
+            Some((i, a)) => (i, Some(a)),
+            None => (value, None)
+        };
+
+        ensure!(!id.is_empty(), "invalid msid!");
+        ensure!(appdata.map(|a| !a.contains(' ')).unwrap_or(true), "invalid msid!");
+        Ok(Self {
+            id,
+            appdata
+        })
+    }
+}
+
+/// Name:  msid-semantic
+/// Value:  msid-semantic-value
+/// Usage Level:  session
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// msid-semantic-value = msid-semantic msid-list
+/// msid-semantic = token ; see RFC 4566
+/// msid-list = *(" " msid-id) / " *"
+///
+/// Example:
+/// a=msid-semantic: WMS 61317484-2ed4-49d7-9eb7-1414322a7aae
+///
+/// The session-level declaration of the media streams, from the early
+/// drafts of the msid specification.  It is no longer part of
+/// [RFC8830](https://datatracker.ietf.org/doc/html/rfc8830), but is still
+/// emitted by browsers, with the "WMS" (WebRTC Media Stream) semantic.
+/// The list of ids is "*" when it applies to all the media streams.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MsidSemantic<'a> {
+    pub semantic: &'a str,
+    pub ids: Vec<&'a str>
+}
+
+impl<'a> fmt::Display for MsidSemantic<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let semantic = MsidSemantic {
+    ///     semantic: "WMS",
+    ///     ids: vec!["stream"]
+    /// };
+    ///
+    /// assert_eq!(format!("{}", semantic), " WMS stream");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " {}", self.semantic)?;
+        for id in &self.ids {
+            write!(f, " {}", id)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MsidSemantic<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let semantic: MsidSemantic = MsidSemantic::try_from(" WMS a b").unwrap();
+    /// assert_eq!(semantic.semantic, "WMS");
+    /// assert_eq!(semantic.ids, vec!["a", "b"]);
+    ///
+    /// let semantic: MsidSemantic = MsidSemantic::try_from("WMS *").unwrap();
+    /// assert_eq!(semantic.ids, vec!["*"]);
+    ///
+    /// let semantic: MsidSemantic = MsidSemantic::try_from(" WMS").unwrap();
+    /// assert!(semantic.ids.is_empty());
+    ///
+    /// assert!(MsidSemantic::try_from(" ").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut values = value.split(' ').filter(|x| !x.is_empty());
+        let semantic = values
+            .next()
+            .ok_or_else(|| anyhow!("invalid msid semantic!"))?;
+        Ok(Self {
+            semantic,
+            ids: values.collect()
+        })
+    }
+}
@@ -0,0 +1,407 @@
+use anyhow::{
+    Result,
+    ensure,
+    anyhow
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// ptp version.
+///
+/// ptp-version = "IEEE1588-2002"
+///             / "IEEE1588-2008"
+///             / "IEEE802.1AS-2011"
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PtpVersion {
+    Ieee1588_2002,
+    Ieee1588_2008,
+    Ieee8021As2011
+}
+
+/// ptp server.
+///
+/// ptp-server = ptp-gmid [":" ptp-domain]
+///            / "traceable"
+///
+/// The grandmaster clock identity is an EUI-64 written as upper-case
+/// hexadecimal octets separated by hyphens, such as
+/// "39-A7-94-FF-FE-07-CB-D0".
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PtpServer<'a> {
+    Traceable,
+    GmId {
+        gmid: &'a str,
+        domain: Option<&'a str>
+    }
+}
+
+/// Name:  ts-refclk
+/// Value:  clksrc
+/// Usage Level:  session, media, source
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// clksrc = ntp / ptp / gps / gal / glonass / local / private / clksrc-ext
+///
+/// Example:
+/// a=ts-refclk:ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:0
+///
+/// The reference clock used for the timestamps of the stream,
+/// [RFC7273](https://datatracker.ietf.org/doc/html/rfc7273).  Multiple
+/// "a=ts-refclk:" attributes may be given for the same scope, they
+/// describe alternative clock sources that are traceable to the same
+/// reference.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RefClk<'a> {
+    /// ntp = "ntp=" ntp-server, "/traceable/" for any traceable server.
+    Ntp(&'a str),
+    Ptp {
+        version: PtpVersion,
+        server: PtpServer<'a>
+    },
+    Gps,
+    Gal,
+    Glonass,
+    Local,
+    Private {
+        traceable: bool
+    },
+    /// clksrc-ext = clksrc-param-name "=" clksrc-param-value
+    Ext(&'a str, &'a str)
+}
+
+/// Name:  mediaclk
+/// Value:  mediaclock
+/// Usage Level:  session, media, source
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// mediaclock = media-clock-source
+/// media-clock-source = "direct" ["=" offset] [SP rate]
+///                    / "sender"
+///                    / "IEEE1722=" stream-id
+/// rate = "rate=" integer "/" integer
+///
+/// Example:
+/// a=mediaclk:direct=963214424 rate=1000/1001
+///
+/// The relationship between the media clock and the reference clock
+/// given by "a=ts-refclk:".
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MediaClk<'a> {
+    Direct {
+        offset: Option<u64>,
+        rate: Option<(u32, u32)>
+    },
+    Sender,
+    Ieee1722(&'a str)
+}
+
+impl fmt::Display for PtpVersion {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(format!("{}", PtpVersion::Ieee1588_2002), "IEEE1588-2002");
+    /// assert_eq!(format!("{}", PtpVersion::Ieee1588_2008), "IEEE1588-2008");
+    /// assert_eq!(format!("{}", PtpVersion::Ieee8021As2011), "IEEE802.1AS-2011");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Ieee1588_2002 =>  "IEEE1588-2002",
+            Self::Ieee1588_2008 =>  "IEEE1588-2008",
+            Self::Ieee8021As2011 => "IEEE802.1AS-2011"
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PtpVersion {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(PtpVersion::try_from("IEEE1588-2002").unwrap(), PtpVersion::Ieee1588_2002);
+    /// assert_eq!(PtpVersion::try_from("IEEE1588-2008").unwrap(), PtpVersion::Ieee1588_2008);
+    /// assert_eq!(PtpVersion::try_from("IEEE802.1AS-2011").unwrap(), PtpVersion::Ieee8021As2011);
+    /// assert!(PtpVersion::try_from("IEEE1588").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "IEEE1588-2002" =>      Ok(Self::Ieee1588_2002),
+            "IEEE1588-2008" =>      Ok(Self::Ieee1588_2008),
+            "IEEE802.1AS-2011" =>   Ok(Self::Ieee8021As2011),
+            _ => Err(anyhow!("invalid ptp version!"))
+        }
+    }
+}
+
+impl<'a> fmt::Display for PtpServer<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let server = PtpServer::GmId {
+    ///     gmid: "39-A7-94-FF-FE-07-CB-D0",
+    ///     domain: Some("0")
+    /// };
+    ///
+    /// assert_eq!(format!("{}", server), "39-A7-94-FF-FE-07-CB-D0:0");
+    /// assert_eq!(format!("{}", PtpServer::Traceable), "traceable");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Traceable => write!(f, "traceable"),
+            Self::GmId { gmid, domain: None } => write!(f, "{}", gmid),
+            Self::GmId { gmid, domain: Some(d) } => write!(f, "{}:{}", gmid, d)
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PtpServer<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(PtpServer::try_from("traceable").unwrap(), PtpServer::Traceable);
+    /// assert_eq!(
+    ///     PtpServer::try_from("39-A7-94-FF-FE-07-CB-D0:0").unwrap(),
+    ///     PtpServer::GmId {
+    ///         gmid: "39-A7-94-FF-FE-07-CB-D0",
+    ///         domain: Some("0")
+    ///     }
+    /// );
+    ///
+    /// assert!(PtpServer::try_from("39-A7-94-FF-FE-07-CB").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        if value == "traceable" {
+            return Ok(Self::Traceable)
+        }
+
+        let (gmid, domain) = match value.split_once(':') {
+            Some((g, d)) => (g, Some(d)),
+            None => (value, None)
+        };
+
+        ensure!(
+            gmid.split('-').count() == 8 &&
+            gmid.split('-').all(|x| x.len() == 2 && u8::from_str_radix(x, 16).is_ok()),
+            "invalid ptp gmid!"
+        );
+
+        Ok(Self::GmId {
+            gmid,
+            domain
+        })
+    }
+}
+
+impl<'a> fmt::Display for RefClk<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let clk = RefClk::Ptp {
+    ///     version: PtpVersion::Ieee1588_2008,
+    ///     server: PtpServer::GmId {
+    ///         gmid: "39-A7-94-FF-FE-07-CB-D0",
+    ///         domain: Some("0")
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(format!("{}", clk), "ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:0");
+    /// assert_eq!(format!("{}", RefClk::Ntp("203.0.113.10")), "ntp=203.0.113.10");
+    /// assert_eq!(format!("{}", RefClk::Private { traceable: true }), "private:traceable");
+    /// assert_eq!(format!("{}", RefClk::Local), "local");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ntp(server) => write!(f, "ntp={}", server),
+            Self::Ptp { version, server } => write!(f, "ptp={}:{}", version, server),
+            Self::Gps => write!(f, "gps"),
+            Self::Gal => write!(f, "gal"),
+            Self::Glonass => write!(f, "glonass"),
+            Self::Local => write!(f, "local"),
+            Self::Private { traceable: false } => write!(f, "private"),
+            Self::Private { traceable: true } => write!(f, "private:traceable"),
+            Self::Ext(k, v) => write!(f, "{}={}", k, v)
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for RefClk<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(
+    ///     RefClk::try_from("ptp=IEEE1588-2008:39-A7-94-FF-FE-07-CB-D0:0").unwrap(),
+    ///     RefClk::Ptp {
+    ///         version: PtpVersion::Ieee1588_2008,
+    ///         server: PtpServer::GmId {
+    ///             gmid: "39-A7-94-FF-FE-07-CB-D0",
+    ///             domain: Some("0")
+    ///         }
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(
+    ///     RefClk::try_from("ptp=IEEE802.1AS-2011:traceable").unwrap(),
+    ///     RefClk::Ptp {
+    ///         version: PtpVersion::Ieee8021As2011,
+    ///         server: PtpServer::Traceable
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(RefClk::try_from("ntp=/traceable/").unwrap(), RefClk::Ntp("/traceable/"));
+    /// assert_eq!(RefClk::try_from("gps").unwrap(), RefClk::Gps);
+    /// assert_eq!(RefClk::try_from("private").unwrap(), RefClk::Private { traceable: false });
+    /// assert_eq!(RefClk::try_from("private:traceable").unwrap(), RefClk::Private { traceable: true });
+    /// assert_eq!(RefClk::try_from("x-clk=a").unwrap(), RefClk::Ext("x-clk", "a"));
+    /// assert!(RefClk::try_from("ptp=IEEE1588-2008").is_err());
+    /// assert!(RefClk::try_from("atomic").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "gps" =>                Ok(Self::Gps),
+            "gal" =>                Ok(Self::Gal),
+            "glonass" =>            Ok(Self::Glonass),
+            "local" =>              Ok(Self::Local),
+            "private" =>            Ok(Self::Private { traceable: false }),
+            "private:traceable" =>  Ok(Self::Private { traceable: true }),
+            _ => match value.split_once('=') {
+                Some(("ntp", server)) => Ok(Self::Ntp(server)),
+                Some(("ptp", server)) => {
+                    let (version, server) = server
+                        .split_once(':')
+                        .ok_or_else(|| anyhow!("invalid ptp clock source!"))?;
+                    Ok(Self::Ptp {
+                        version: PtpVersion::try_from(version)?,
+                        server: PtpServer::try_from(server)?
+                    })
+                },
+                Some((k, v)) => Ok(Self::Ext(k, v)),
+                None => Err(anyhow!("invalid clock source!"))
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for MediaClk<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let clk = MediaClk::Direct {
+    ///     offset: Some(963214424),
+    ///     rate: Some((1000, 1001))
+    /// };
+    ///
+    /// assert_eq!(format!("{}", clk), "direct=963214424 rate=1000/1001");
+    /// assert_eq!(format!("{}", MediaClk::Sender), "sender");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sender => write!(f, "sender"),
+            Self::Ieee1722(id) => write!(f, "IEEE1722={}", id),
+            Self::Direct { offset, rate } => {
+                write!(f, "direct")?;
+                if let Some(o) = offset {
+                    write!(f, "={}", o)?;
+                }
+
+                if let Some((n, d)) = rate {
+                    write!(f, " rate={}/{}", n, d)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MediaClk<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(
+    ///     MediaClk::try_from("direct=963214424 rate=1000/1001").unwrap(),
+    ///     MediaClk::Direct {
+    ///         offset: Some(963214424),
+    ///         rate: Some((1000, 1001))
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(
+    ///     MediaClk::try_from("direct=0").unwrap(),
+    ///     MediaClk::Direct {
+    ///         offset: Some(0),
+    ///         rate: None
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(MediaClk::try_from("sender").unwrap(), MediaClk::Sender);
+    /// assert_eq!(
+    ///     MediaClk::try_from("IEEE1722=38-D6-6D-8E-D2-78-13-2A").unwrap(),
+    ///     MediaClk::Ieee1722("38-D6-6D-8E-D2-78-13-2A")
+    /// );
+    ///
+    /// assert!(MediaClk::try_from("direct=0 rate=1000").is_err());
+    /// assert!(MediaClk::try_from("receiver").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        if value == "sender" {
+            return Ok(Self::Sender)
+        }
+
+        if let Some(id) = value.strip_prefix("IEEE1722=") {
+            return Ok(Self::Ieee1722(id))
+        }
+
+        let mut values = value.split(' ');
+        let offset = match values.next() {
+            Some("direct") => None,
+            Some(v) => match v.strip_prefix("direct=") {
+                Some(o) => Some(o.parse()?),
+                None => return Err(anyhow!("invalid media clock source!"))
+            },
+            None => return Err(anyhow!("invalid media clock source!"))
+        };
+
+        let rate = match values.next() {
+            None => None,
+            Some(v) => {
+                let (n, d) = v
+                    .strip_prefix("rate=")
+                    .and_then(|r| r.split_once('/'))
+                    .ok_or_else(|| anyhow!("invalid media clock rate!"))?;
+                Some((n.parse()?, d.parse()?))
+            }
+        };
+
+        Ok(Self::Direct {
+            offset,
+            rate
+        })
+    }
+}
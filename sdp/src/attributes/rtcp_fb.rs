@@ -0,0 +1,152 @@
+use super::Attributes;
+use anyhow::{
+    Result,
+    anyhow
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  rtcp-fb
+/// Value:  rtcp-fb-val
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// rtcp-fb-syntax = "a=rtcp-fb:" rtcp-fb-pt SP rtcp-fb-val CRLF
+/// rtcp-fb-pt = "*" ; wildcard: applies to all formats
+///            / fmt ; as defined in SDP spec
+/// rtcp-fb-val = "ack" rtcp-fb-ack-param
+///             / "nack" rtcp-fb-nack-param
+///             / "trr-int" SP 1*DIGIT
+///             / rtcp-fb-id rtcp-fb-param
+///
+/// Example:
+/// a=rtcp-fb:96 nack pli
+///
+/// The RTCP feedback messages supported for a format,
+/// [RFC4585](https://datatracker.ietf.org/doc/html/rfc4585#section-4.2).
+/// The payload type is `None` for the wildcard "*".
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RtcpFb<'a> {
+    pub pt: Option<u8>,
+    pub kind: &'a str,
+    pub param: Option<&'a str>
+}
+
+impl<'a> RtcpFb<'a> {
+    /// whether this is the Chrome receiver estimated maximum bitrate
+    /// feedback, "goog-remb".
+    pub fn is_goog_remb(&self) -> bool {
+        self.kind == "goog-remb"
+    }
+}
+
+impl<'a> Attributes<'a> {
+    /// get the feedback of a payload type.
+    ///
+    /// includes the wildcard "*" feedback which applies to all formats.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("rtcp-fb:96 goog-remb").unwrap();
+    /// attributes.handle("rtcp-fb:96 nack pli").unwrap();
+    /// attributes.handle("rtcp-fb:* transport-cc").unwrap();
+    ///
+    /// assert_eq!(attributes.feedback(96).count(), 3);
+    /// assert_eq!(attributes.feedback(97).count(), 1);
+    /// assert!(attributes.goog_remb(96));
+    /// assert!(!attributes.goog_remb(97));
+    /// ```
+    pub fn feedback(&self, pt: u8) -> impl Iterator<Item = &RtcpFb<'a>> {
+        self.rtcp_fb
+            .iter()
+            .filter(move |fb| fb.pt.map(|x| x == pt).unwrap_or(true))
+    }
+
+    /// whether the payload type uses the "goog-remb" feedback.
+    pub fn goog_remb(&self, pt: u8) -> bool {
+        self.feedback(pt).any(|fb| fb.is_goog_remb())
+    }
+}
+
+impl<'a> fmt::Display for RtcpFb<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let fb = RtcpFb {
+    ///     pt: Some(96),
+    ///     kind: "nack",
+    ///     param: Some("pli")
+    /// };
+    ///
+    /// assert_eq!(format!("{}", fb), "96 nack pli");
+    ///
+    /// let fb = RtcpFb {
+    ///     pt: None,
+    ///     kind: "transport-cc",
+    ///     param: None
+    /// };
+    ///
+    /// assert_eq!(format!("{}", fb), "* transport-cc");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pt {
+            Some(pt) => write!(f, "{} {}", pt, self.kind)?,
+            None => write!(f, "* {}", self.kind)?
+        }
+
+        if let Some(param) = self.param {
+            write!(f, " {}", param)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for RtcpFb<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let fb: RtcpFb = RtcpFb::try_from("96 nack pli").unwrap();
+    /// assert_eq!(fb.pt, Some(96));
+    /// assert_eq!(fb.kind, "nack");
+    /// assert_eq!(fb.param, Some("pli"));
+    ///
+    /// let fb: RtcpFb = RtcpFb::try_from("* ccm fir").unwrap();
+    /// assert_eq!(fb.pt, None);
+    /// assert_eq!(fb.kind, "ccm");
+    ///
+    /// assert!(RtcpFb::try_from("96").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (pt, value) = value
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid rtcp-fb!"))?;
+        let (kind, param) = match value.split_once(' ') {
+            Some((k, p)) => (k, Some(p)),
+            None => (value, None)
+        };
+
+        Ok(Self {
+            pt: match pt {
+                "*" => None,
+                _ => Some(pt.parse()?)
+            },
+            kind,
+            param
+        })
+    }
+}
@@ -1,8 +1,6 @@
+use crate::util::next_field;
 use super::Codec;
-use anyhow::{
-    Result,
-    ensure
-};
+use anyhow::Result;
 
 use std::{
     convert::TryFrom, 
@@ -69,7 +67,7 @@ use std::{
 /// [RFC3551](https://datatracker.ietf.org/doc/html/rfc3551)) 
 /// packetization is required, the "a=ptime:" attribute is used as given 
 /// in [Section 6.4](https://datatracker.ietf.org/doc/html/rfc8866#section-6.4).
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RtpValue {
     pub codec: Codec,
     pub frequency: Option<u64>,
@@ -121,12 +119,11 @@ impl<'a> TryFrom<&'a str> for RtpValue {
     /// assert_eq!(value.channels, None);
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let values = value.split('/').collect::<Vec<&str>>();
-        ensure!(!values.is_empty(), "invalid attributes rtpmap!");
+        let mut split = value.split('/');
         Ok(Self {
-            codec: Codec::try_from(values[0])?,
-            frequency: if let Some(c) = values.get(1) { Some(c.parse()?) } else { None },
-            channels: if let Some(c) = values.get(2) { Some(c.parse()?) } else { None }
+            codec: Codec::try_from(next_field(&mut split, "invalid attributes rtpmap!")?)?,
+            frequency: split.next().map(str::parse).transpose()?,
+            channels: split.next().map(str::parse).transpose()?
         })
     }
 }
@@ -0,0 +1,189 @@
+use anyhow::anyhow;
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  setup
+/// Value:  role
+/// Usage Level:  session, media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// setup-attr = "a=setup:" role
+/// role = "active" / "passive" / "actpass" / "holdconn"
+///
+/// Example:
+/// a=setup:actpass
+///
+/// The 'setup' attribute indicates which of the end points should
+/// initiate the TCP connection establishment (i.e., send the initial TCP
+/// SYN), [RFC4145](https://datatracker.ietf.org/doc/html/rfc4145#section-4).
+/// The same attribute is used to negotiate the DTLS roles.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Setup {
+    /// The endpoint will initiate an outgoing connection.
+    Active,
+    /// The endpoint will accept an incoming connection.
+    Passive,
+    /// The endpoint is willing to accept an incoming connection or
+    /// to initiate an outgoing connection.
+    ActPass,
+    /// The endpoint does not want the connection to be established
+    /// for the time being.
+    HoldConn
+}
+
+/// Name:  connection
+/// Value:  conn-value
+/// Usage Level:  session, media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// connection-attr = "a=connection:" conn-value
+/// conn-value = "new" / "existing"
+///
+/// Example:
+/// a=connection:new
+///
+/// Whether a new TCP connection is to be established, or an existing
+/// one is to be reused, [RFC4145](https://datatracker.ietf.org/doc/html/rfc4145#section-5).
+/// When the attribute is absent, a new connection is assumed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TcpConnection {
+    New,
+    Existing
+}
+
+impl Setup {
+    /// the role of the answer.
+    ///
+    /// the answerer picks "active" for an "actpass" offer, and the
+    /// opposite role otherwise.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(Setup::ActPass.answer(), Setup::Active);
+    /// assert_eq!(Setup::Active.answer(), Setup::Passive);
+    /// assert_eq!(Setup::Passive.answer(), Setup::Active);
+    /// assert_eq!(Setup::HoldConn.answer(), Setup::HoldConn);
+    /// ```
+    pub fn answer(self) -> Self {
+        match self {
+            Self::Active => Self::Passive,
+            Self::Passive => Self::Active,
+            Self::ActPass => Self::Active,
+            Self::HoldConn => Self::HoldConn
+        }
+    }
+
+    /// whether the answer role is valid for the offer role.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert!(Setup::ActPass.accepts(Setup::Passive));
+    /// assert!(Setup::Active.accepts(Setup::Passive));
+    /// assert!(!Setup::Active.accepts(Setup::Active));
+    /// assert!(!Setup::ActPass.accepts(Setup::ActPass));
+    /// ```
+    pub fn accepts(self, answer: Self) -> bool {
+        matches!(
+            (self, answer),
+            (Self::ActPass, Self::Active) |
+            (Self::ActPass, Self::Passive) |
+            (Self::Active, Self::Passive) |
+            (Self::Passive, Self::Active) |
+            (Self::HoldConn, Self::HoldConn)
+        )
+    }
+}
+
+impl fmt::Display for Setup {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(format!("{}", Setup::Active), "active");
+    /// assert_eq!(format!("{}", Setup::Passive), "passive");
+    /// assert_eq!(format!("{}", Setup::ActPass), "actpass");
+    /// assert_eq!(format!("{}", Setup::HoldConn), "holdconn");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Active =>     "active",
+            Self::Passive =>    "passive",
+            Self::ActPass =>    "actpass",
+            Self::HoldConn =>   "holdconn"
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Setup {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(Setup::try_from("active").unwrap(), Setup::Active);
+    /// assert_eq!(Setup::try_from("passive").unwrap(), Setup::Passive);
+    /// assert_eq!(Setup::try_from("actpass").unwrap(), Setup::ActPass);
+    /// assert_eq!(Setup::try_from("holdconn").unwrap(), Setup::HoldConn);
+    /// assert!(Setup::try_from("ACTIVE").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "active" =>     Ok(Self::Active),
+            "passive" =>    Ok(Self::Passive),
+            "actpass" =>    Ok(Self::ActPass),
+            "holdconn" =>   Ok(Self::HoldConn),
+            _ => Err(anyhow!("invalid setup!"))
+        }
+    }
+}
+
+impl fmt::Display for TcpConnection {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// assert_eq!(format!("{}", TcpConnection::New), "new");
+    /// assert_eq!(format!("{}", TcpConnection::Existing), "existing");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::New =>        "new",
+            Self::Existing =>   "existing"
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for TcpConnection {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(TcpConnection::try_from("new").unwrap(), TcpConnection::New);
+    /// assert_eq!(TcpConnection::try_from("existing").unwrap(), TcpConnection::Existing);
+    /// assert!(TcpConnection::try_from("reuse").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "new" =>        Ok(Self::New),
+            "existing" =>   Ok(Self::Existing),
+            _ => Err(anyhow!("invalid tcp connection!"))
+        }
+    }
+}
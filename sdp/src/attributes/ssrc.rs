@@ -0,0 +1,260 @@
+use anyhow::{
+    Result,
+    ensure,
+    anyhow
+};
+
+use super::{
+    Attributes,
+    Msid
+};
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  ssrc
+/// Value:  ssrc-attr
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// ssrc-attr = "ssrc:" ssrc-id SP attribute
+/// ; The base definition of "attribute" is in RFC 4566.
+/// ; (It is the content of "a=" lines.)
+/// ssrc-id = integer ; 0 .. 2**32 - 1
+///
+/// Example:
+/// a=ssrc:314159 cname:user@example.com
+///
+/// The SDP media attribute "ssrc" indicates a property (known as a
+/// "source-level attribute") of a media source (RTP stream) within an
+/// RTP session, [RFC5576](https://datatracker.ietf.org/doc/html/rfc5576).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ssrc<'a> {
+    pub id: u32,
+    pub attribute: &'a str,
+    pub value: Option<&'a str>
+}
+
+/// Name:  ssrc-group
+/// Value:  ssrc-group-attr
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// ssrc-group-attr = "ssrc-group:" semantics *(SP ssrc-id)
+/// semantics = "FEC" / "FID" / token
+///
+/// Example:
+/// a=ssrc-group:FID 314159 271828
+///
+/// The "ssrc-group" attribute expresses a relationship among several
+/// sources of an RTP session, such as a media source and its
+/// retransmission source.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SsrcGroup<'a> {
+    pub semantics: &'a str,
+    pub ssrcs: Vec<u32>
+}
+
+/// source description.
+///
+/// the source-level attributes of an SSRC joined with its group and
+/// the media stream it belongs to, see `Attributes::sources`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Source<'a> {
+    pub cname: Option<&'a str>,
+    /// the media stream identifier.
+    pub stream: Option<&'a str>,
+    /// the track identifier.
+    pub track: Option<&'a str>,
+    /// the other SSRC of the "FID" group, the retransmission SSRC of
+    /// a media source, or the media source of a retransmission SSRC.
+    pub rtx: Option<u32>
+}
+
+impl<'a> Attributes<'a> {
+    /// get the sources.
+    ///
+    /// joins "a=ssrc:", "a=ssrc-group:FID" and "a=msid:".  the
+    /// source-level "msid" of Plan B takes precedence over the 
+    /// media-level "a=msid:" of Unified Plan.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let mut attributes = Attributes::default();
+    /// attributes.handle("msid:stream track").unwrap();
+    /// attributes.handle("ssrc-group:FID 314159 271828").unwrap();
+    /// attributes.handle("ssrc:314159 cname:user@example.com").unwrap();
+    /// attributes.handle("ssrc:271828 cname:user@example.com").unwrap();
+    /// attributes.handle("ssrc:161803 cname:other@example.com").unwrap();
+    /// attributes.handle("ssrc:161803 msid:other-stream other-track").unwrap();
+    ///
+    /// let sources = attributes.sources();
+    /// assert_eq!(sources.len(), 3);
+    /// assert_eq!(sources[&314159], Source {
+    ///     cname: Some("user@example.com"),
+    ///     stream: Some("stream"),
+    ///     track: Some("track"),
+    ///     rtx: Some(271828)
+    /// });
+    ///
+    /// assert_eq!(sources[&271828].rtx, Some(314159));
+    /// assert_eq!(sources[&161803].stream, Some("other-stream"));
+    /// assert_eq!(sources[&161803].track, Some("other-track"));
+    /// assert_eq!(sources[&161803].rtx, None);
+    /// ```
+    pub fn sources(&self) -> HashMap<u32, Source<'a>> {
+        let msid = self.msid.first();
+        let mut sources: HashMap<u32, Source<'a>> = HashMap::new();
+        for ssrc in &self.ssrcs {
+            let source = sources.entry(ssrc.id).or_insert_with(|| Source {
+                stream: msid.map(|m| m.id),
+                track: msid.and_then(|m| m.appdata),
+                ..Default::default()
+            });
+
+            match (ssrc.attribute, ssrc.value) {
+                ("cname", Some(cname)) => source.cname = Some(cname),
+                ("msid", Some(value)) => if let Ok(m) = Msid::try_from(value) {
+                    source.stream = Some(m.id);
+                    source.track = m.appdata;
+                },
+                _ => ()
+            }
+        }
+
+        for group in self.ssrc_groups.iter().filter(|g| g.semantics == "FID") {
+            if let [media, rtx] = group.ssrcs[..] {
+                if let Some(source) = sources.get_mut(&media) {
+                    source.rtx = Some(rtx);
+                }
+
+                if let Some(source) = sources.get_mut(&rtx) {
+                    source.rtx = Some(media);
+                }
+            }
+        }
+
+        sources
+    }
+}
+
+impl<'a> fmt::Display for Ssrc<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let ssrc = Ssrc {
+    ///     id: 314159,
+    ///     attribute: "msid",
+    ///     value: Some("stream track")
+    /// };
+    ///
+    /// assert_eq!(format!("{}", ssrc), "314159 msid:stream track");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.attribute)?;
+        if let Some(value) = self.value {
+            write!(f, ":{}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Ssrc<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let ssrc: Ssrc = Ssrc::try_from("314159 msid:stream track").unwrap();
+    ///
+    /// assert_eq!(ssrc.id, 314159);
+    /// assert_eq!(ssrc.attribute, "msid");
+    /// assert_eq!(ssrc.value, Some("stream track"));
+    ///
+    /// let ssrc: Ssrc = Ssrc::try_from("314159 previous-ssrc").unwrap();
+    /// assert_eq!(ssrc.value, None);
+    ///
+    /// assert!(Ssrc::try_from("314159").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (id, attribute) = value
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid ssrc!"))?;
+        let (attribute, value) = match attribute.split_once(':') {
+            Some((a, v)) => (a, Some(v)),
+            None => (attribute, None)
+        };
+
+        ensure!(!attribute.is_empty(), "invalid ssrc!");
+        Ok(Self {
+            id: id.parse()?,
+            attribute,
+            value
+        })
+    }
+}
+
+impl<'a> fmt::Display for SsrcGroup<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let group = SsrcGroup {
+    ///     semantics: "FID",
+    ///     ssrcs: vec![314159, 271828]
+    /// };
+    ///
+    /// assert_eq!(format!("{}", group), "FID 314159 271828");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.semantics)?;
+        for ssrc in &self.ssrcs {
+            write!(f, " {}", ssrc)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SsrcGroup<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let group: SsrcGroup = SsrcGroup::try_from("FID 314159 271828").unwrap();
+    ///
+    /// assert_eq!(group.semantics, "FID");
+    /// assert_eq!(group.ssrcs, vec![314159, 271828]);
+    /// assert!(SsrcGroup::try_from("FID 314159 rtx").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut values = value.split(' ');
+        let semantics = values
+            .next()
+            .filter(|x| !x.is_empty())
+            .ok_or_else(|| anyhow!("invalid ssrc group!"))?;
+        Ok(Self {
+            semantics,
+            ssrcs: values
+                .map(|x| x.parse())
+                .collect::<Result<Vec<u32>, _>>()?
+        })
+    }
+}
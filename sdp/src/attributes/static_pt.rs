@@ -0,0 +1,63 @@
+use super::{
+    RtpValue,
+    Codec
+};
+
+/// get the format of a static payload type.
+///
+/// the payload types statically assigned by the RTP/AVP profile,
+/// [RFC3551](https://datatracker.ietf.org/doc/html/rfc3551#section-6),
+/// which a media description can use without "a=rtpmap:".
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::attributes::*;
+///
+/// let pcmu = static_payload_type(0).unwrap();
+/// assert_eq!(pcmu.codec, Codec::Pcmu);
+/// assert_eq!(pcmu.frequency, Some(8000));
+/// assert_eq!(pcmu.channels, Some(1));
+///
+/// assert_eq!(static_payload_type(18).unwrap().codec, Codec::G729);
+/// assert_eq!(static_payload_type(34).unwrap().frequency, Some(90000));
+/// assert_eq!(static_payload_type(34).unwrap().channels, None);
+/// assert!(static_payload_type(2).is_none());
+/// assert!(static_payload_type(96).is_none());
+/// ```
+#[rustfmt::skip]
+pub fn static_payload_type(pt: u8) -> Option<RtpValue> {
+    let (codec, frequency, channels) = match pt {
+        0 =>  (Codec::Pcmu,  8000,  Some(1)),
+        3 =>  (Codec::Gsm,   8000,  Some(1)),
+        4 =>  (Codec::G723,  8000,  Some(1)),
+        5 =>  (Codec::Dvi4,  8000,  Some(1)),
+        6 =>  (Codec::Dvi4,  16000, Some(1)),
+        7 =>  (Codec::Lpc,   8000,  Some(1)),
+        8 =>  (Codec::Pcma,  8000,  Some(1)),
+        9 =>  (Codec::G722,  8000,  Some(1)),
+        10 => (Codec::L16,   44100, Some(2)),
+        11 => (Codec::L16,   44100, Some(1)),
+        12 => (Codec::Qcelp, 8000,  Some(1)),
+        13 => (Codec::Cn,    8000,  Some(1)),
+        14 => (Codec::Mpa,   90000, None),
+        15 => (Codec::G728,  8000,  Some(1)),
+        16 => (Codec::Dvi4,  11025, Some(1)),
+        17 => (Codec::Dvi4,  22050, Some(1)),
+        18 => (Codec::G729,  8000,  Some(1)),
+        25 => (Codec::CelB,  90000, None),
+        26 => (Codec::Jpeg,  90000, None),
+        28 => (Codec::Nv,    90000, None),
+        31 => (Codec::H261,  90000, None),
+        32 => (Codec::Mpv,   90000, None),
+        33 => (Codec::Mp2t,  90000, None),
+        34 => (Codec::H263,  90000, None),
+        _ => return None
+    };
+
+    Some(RtpValue {
+        codec,
+        frequency: Some(frequency),
+        channels
+    })
+}
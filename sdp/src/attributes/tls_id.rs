@@ -0,0 +1,64 @@
+use anyhow::ensure;
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Name:  tls-id
+/// Value:  tls-id-value
+/// Usage Level:  media
+/// Charset Dependent:  no
+///
+/// Syntax:
+/// tls-id-value = 20*255(tls-id-char)
+/// tls-id-char  = ALPHA / DIGIT / "+" / "/" / "-" / "_"
+///
+/// Example:
+/// a=tls-id:abc3de65cddef001be82
+///
+/// The identifier of the DTLS association,
+/// [RFC8842](https://datatracker.ietf.org/doc/html/rfc8842#section-5).
+/// A new value is generated when an endpoint wants a new DTLS
+/// association, an unchanged value across an ICE restart means the
+/// existing association is kept.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TlsId<'a>(pub &'a str);
+
+impl<'a> fmt::Display for TlsId<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    ///
+    /// let id = TlsId("abc3de65cddef001be82");
+    /// assert_eq!(format!("{}", id), "abc3de65cddef001be82");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for TlsId<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::*;
+    /// use std::convert::*;
+    ///
+    /// let id: TlsId = TlsId::try_from("abc3de65cddef001be82").unwrap();
+    /// assert_eq!(id.0, "abc3de65cddef001be82");
+    ///
+    /// assert!(TlsId::try_from("abc3de65").is_err());
+    /// assert!(TlsId::try_from("abc3de65cddef001be82!").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        ensure!(
+            (20..=255).contains(&value.len()) &&
+            value.chars().all(|c| c.is_ascii_alphanumeric() || "+/-_".contains(c)),
+            "invalid tls-id!"
+        );
+
+        Ok(Self(value))
+    }
+}
@@ -1,4 +1,9 @@
 use super::util::tuple2_from_split;
+use super::writer::{
+    LineEnding,
+    Writer
+};
+
 use anyhow::anyhow;
 use std::{
     convert::TryFrom,
@@ -9,7 +14,8 @@ use std::{
 #[derive(Debug, PartialEq, Eq)]
 pub enum BwKind {
     CT,
-    AS
+    AS,
+    TIAS
 }
 
 /// Bandwidth
@@ -39,6 +45,104 @@ pub struct Bandwidth {
     pub bandwidth: usize
 }
 
+/// Bandwidths
+///
+/// A session or media description can carry several "b=" lines, one
+/// for each <bwtype>.  A peer receiving an unknown <bwtype> MUST ignore
+/// it, so only the known modifiers are kept here.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::bandwidth::*;
+///
+/// let mut bandwidths = Bandwidths::default();
+/// bandwidths.handle("CT:1024").unwrap();
+/// bandwidths.handle("AS:512").unwrap();
+///
+/// assert_eq!(bandwidths.ct, Some(1024));
+/// assert_eq!(bandwidths.r#as, Some(512));
+/// assert_eq!(bandwidths.tias, None);
+/// ```
+#[derive(Debug, Default)]
+pub struct Bandwidths {
+    /// b=CT:<bandwidth>, conference total in kilobits per second.
+    pub ct: Option<usize>,
+    /// b=AS:<bandwidth>, application specific maximum in kilobits per second.
+    pub r#as: Option<usize>,
+    /// TIAS Transport Independent Application Specific Maximum, 
+    /// [RFC3890](https://datatracker.ietf.org/doc/html/rfc3890).
+    ///
+    /// The TIAS bandwidth modifier has an integer bit-rate value in bits
+    /// per second.  A fractional bandwidth value SHALL always be rounded
+    /// up to the next integer.  The bandwidth value is the maximum needed
+    /// by the application (SDP session level) or media stream (SDP media
+    /// level) without counting IP and other transport layers like TCP or
+    /// UDP.
+    pub tias: Option<usize>,
+}
+
+impl Bandwidths {
+    /// handle the value of a "b=" line, the text after "b=".
+    ///
+    /// a repeated <bwtype> replaces the previous value, an unknown
+    /// <bwtype> is ignored, but a known one with an invalid
+    /// <bandwidth> is an error.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::bandwidth::*;
+    ///
+    /// let mut bandwidths = Bandwidths::default();
+    /// bandwidths.handle("AS:512").unwrap();
+    /// bandwidths.handle("TIAS:500000").unwrap();
+    /// bandwidths.handle("X-YZ:128").unwrap();
+    ///
+    /// assert_eq!(bandwidths.ct, None);
+    /// assert_eq!(bandwidths.r#as, Some(512));
+    /// assert_eq!(bandwidths.tias, Some(500000));
+    /// assert!(bandwidths.handle("AS:max").is_err());
+    /// ```
+    pub fn handle(&mut self, value: &str) -> anyhow::Result<()> {
+        let (t, w) = tuple2_from_split(value, ':', "invalid band width!")?;
+        let kind = match BwKind::try_from(t) {
+            Ok(k) => k,
+            _ => return Ok(())
+        };
+
+        let bandwidth = Some(w.parse()?);
+        match kind {
+            BwKind::CT => self.ct = bandwidth,
+            BwKind::AS => self.r#as = bandwidth,
+            BwKind::TIAS => self.tias = bandwidth,
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Bandwidths {
+    /// the "b=" lines with CRLF line endings, see `Writer::bandwidths`.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::bandwidth::*;
+    ///
+    /// let bandwidths = Bandwidths {
+    ///     ct: None,
+    ///     r#as: Some(512),
+    ///     tias: Some(500000),
+    /// };
+    ///
+    /// assert_eq!(format!("{}", bandwidths), "b=AS:512\r\nb=TIAS:500000\r\n");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Writer::new(f, LineEnding::Crlf).bandwidths(self)
+    }
+}
+
 impl fmt::Display for Bandwidth {
     /// # Unit Test
     ///
@@ -93,11 +197,13 @@ impl fmt::Display for BwKind {
     /// use sdp::bandwidth::*;
     ///
     /// assert_eq!(format!("{}", BwKind::AS), "AS");
+    /// assert_eq!(format!("{}", BwKind::TIAS), "TIAS");
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", match self {
             Self::CT => "CT",
-            Self::AS => "AS"
+            Self::AS => "AS",
+            Self::TIAS => "TIAS"
         })
     }
 }
@@ -112,11 +218,14 @@ impl<'a> TryFrom<&'a str> for BwKind {
     ///
     /// let kind: BwKind = BwKind::try_from("AS").unwrap();
     /// assert_eq!(kind, BwKind::AS);
+    /// assert_eq!(BwKind::try_from("TIAS").unwrap(), BwKind::TIAS);
+    /// assert!(BwKind::try_from("X-YZ").is_err());
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         match value {
             "CT" => Ok(Self::CT),
             "AS" => Ok(Self::AS),
+            "TIAS" => Ok(Self::TIAS),
             _ => Err(anyhow!("invalid band width type!"))
         }
     }
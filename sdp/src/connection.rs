@@ -1,6 +1,9 @@
-use super::util::tuple3_from_split;
+use super::util::{
+    tuple3_from_split,
+    next_field
+};
+
 use std::net::IpAddr;
-use anyhow::ensure;
 use super::{
     NetKind,
     AddrKind
@@ -150,12 +153,11 @@ impl<'a> TryFrom<&'a str> for Addr {
     /// assert_eq!(instance.count, Some(2));
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let values = value.split('/').collect::<Vec<&str>>();
-        ensure!(!values.is_empty(), "invalid connection information!");
+        let mut split = value.split('/');
         Ok(Self {
-            ip: values[0].parse()?,
-            ttl: if let Some(t) = values.get(1) { Some(t.parse()?) } else { None },
-            count: if let Some(c) =  values.get(2) { Some(c.parse()?) } else { None}
+            ip: next_field(&mut split, "invalid connection information!")?.parse()?,
+            ttl: split.next().map(str::parse).transpose()?,
+            count: split.next().map(str::parse).transpose()?
         })
     }
 }
@@ -0,0 +1,415 @@
+use super::attributes::{
+    Attribute,
+    Attributes,
+    Direction,
+    Key
+};
+
+use super::media::MediaDescription;
+use super::Sdp;
+use std::convert::TryFrom;
+
+/// the changes of a media section present in both sessions.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MediaDiff {
+    /// index of the media section ("m=" line).
+    pub index: usize,
+    /// payload types only present in the new session.
+    pub added_codecs: Vec<u8>,
+    /// payload types only present in the old session.
+    pub removed_codecs: Vec<u8>,
+    /// payload types mapped to a different codec, clock rate
+    /// or channel count, or with different format parameters
+    /// ("a=fmtp:") or feedback messages ("a=rtcp-fb:").
+    pub changed_codecs: Vec<u8>,
+    /// the effective direction, old and new, if it changed.
+    pub direction: Option<(Direction, Direction)>,
+    /// the effective ICE username fragment or password changed.
+    pub ice_restart: bool,
+    /// the DTLS association identifier changed, a new DTLS
+    /// association is established.
+    pub tls_id_changed: bool,
+}
+
+/// media section change.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MediaChange {
+    /// a media section was appended to the session.
+    Added(usize),
+    /// a media section was removed from the session, either
+    /// dropped or rejected by setting its port to zero.
+    Removed(usize),
+    /// a media section was modified.
+    Changed(MediaDiff),
+}
+
+/// the differences between two session descriptions.
+///
+/// Media sections are matched by their position, since an offer
+/// or answer can never reorder or remove existing "m=" lines, a
+/// media section that is no longer used is recycled or rejected
+/// with a zero port.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Diff {
+    pub media: Vec<MediaChange>,
+}
+
+impl Diff {
+    /// whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.media.is_empty()
+    }
+
+    /// whether the renegotiation requires an ICE restart.
+    ///
+    /// An ICE restart is signaled by a change of the ICE username
+    /// fragment or password of a media section.
+    pub fn ice_restart(&self) -> bool {
+        self.media.iter().any(|change| match change {
+            MediaChange::Changed(diff) => diff.ice_restart,
+            _ => false
+        })
+    }
+
+    /// whether the DTLS association is reused.
+    ///
+    /// an ICE restart keeps the existing DTLS association, unless the
+    /// "a=tls-id:" attribute of a media section changed.  without an
+    /// ICE restart nothing is renegotiated.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::diff::diff;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let old = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     a=ice-ufrag:F7gI\r\n\
+    ///     a=tls-id:abc3de65cddef001be82\r\n"
+    /// ).unwrap();
+    ///
+    /// let restart = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     a=ice-ufrag:Kz1q\r\n\
+    ///     a=tls-id:abc3de65cddef001be82\r\n"
+    /// ).unwrap();
+    ///
+    /// let renegotiate = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     a=ice-ufrag:Kz1q\r\n\
+    ///     a=tls-id:0a1b2c3d4e5f6a7b8c9d\r\n"
+    /// ).unwrap();
+    ///
+    /// assert!(diff(&old, &restart).ice_restart());
+    /// assert!(diff(&old, &restart).dtls_reused());
+    /// assert!(!diff(&old, &renegotiate).dtls_reused());
+    /// ```
+    pub fn dtls_reused(&self) -> bool {
+        self.media.iter().all(|change| match change {
+            MediaChange::Changed(diff) => !diff.tls_id_changed,
+            _ => true
+        })
+    }
+}
+
+/// compare two session descriptions.
+///
+/// reports added, removed and changed media sections, and for the
+/// changed media the codecs, direction and ICE credentials.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::diff::*;
+/// use sdp::attributes::Direction;
+/// use sdp::Sdp;
+/// use std::convert::*;
+///
+/// let old = Sdp::try_from(
+///     "v=0\r\n\
+///     a=ice-ufrag:8hhY\r\n\
+///     a=ice-pwd:asd88fgpdd777uzjYhagZg\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 98\r\n\
+///     a=rtpmap:96 VP8/90000\r\n\
+///     a=rtpmap:98 VP9/90000\r\n"
+/// ).unwrap();
+///
+/// let new = Sdp::try_from(
+///     "v=0\r\n\
+///     a=ice-ufrag:8hhY\r\n\
+///     a=ice-pwd:asd88fgpdd777uzjYhagZg\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 100\r\n\
+///     a=rtpmap:96 VP8/90000\r\n\
+///     a=rtpmap:100 H264/90000\r\n\
+///     a=sendonly\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+///     a=rtpmap:96 VP8/90000\r\n"
+/// ).unwrap();
+///
+/// assert!(diff(&old, &old).is_empty());
+///
+/// let changes = diff(&old, &new);
+/// assert!(!changes.ice_restart());
+/// assert_eq!(changes.media, vec![
+///     MediaChange::Changed(MediaDiff {
+///         index: 0,
+///         added_codecs: vec![100],
+///         removed_codecs: vec![98],
+///         changed_codecs: vec![],
+///         direction: Some((Direction::SendRecv, Direction::SendOnly)),
+///         ice_restart: false,
+///         tls_id_changed: false,
+///     }),
+///     MediaChange::Added(1),
+/// ]);
+///
+/// let restart = Sdp::try_from(
+///     "v=0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 98\r\n\
+///     a=ice-ufrag:Kz1q\r\n\
+///     a=ice-pwd:asd88fgpdd777uzjYhagZg\r\n\
+///     a=rtpmap:96 VP8/90000\r\n\
+///     a=rtpmap:98 VP9/90000\r\n"
+/// ).unwrap();
+///
+/// assert!(diff(&old, &restart).ice_restart());
+/// assert_eq!(diff(&new, &old).media[1], MediaChange::Removed(1));
+///
+/// let old = Sdp::try_from(
+///     "v=0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 97\r\n\
+///     a=rtpmap:97 H264/90000\r\n\
+///     a=rtcp-fb:97 nack\r\n\
+///     a=fmtp:97 packetization-mode=0\r\n"
+/// ).unwrap();
+///
+/// let mode = Sdp::try_from(
+///     "v=0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 97\r\n\
+///     a=rtpmap:97 H264/90000\r\n\
+///     a=rtcp-fb:97 nack\r\n\
+///     a=fmtp:97 packetization-mode=1\r\n"
+/// ).unwrap();
+///
+/// let feedback = Sdp::try_from(
+///     "v=0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 97\r\n\
+///     a=rtpmap:97 H264/90000\r\n\
+///     a=rtcp-fb:97 nack pli\r\n\
+///     a=fmtp:97 packetization-mode=0\r\n"
+/// ).unwrap();
+///
+/// let changed = |new: &Sdp| match &diff(&old, new).media[..] {
+///     [MediaChange::Changed(d)] => d.changed_codecs.clone(),
+///     _ => vec![]
+/// };
+///
+/// assert_eq!(changed(&mode), vec![97]);
+/// assert_eq!(changed(&feedback), vec![97]);
+/// ```
+pub fn diff(old: &Sdp, new: &Sdp) -> Diff {
+    let mut media = Vec::new();
+    let size = std::cmp::max(old.media.len(), new.media.len());
+    for index in 0..size {
+        match (old.media.get(index), new.media.get(index)) {
+            (Some(o), Some(n)) if o.media.port.num != 0 && n.media.port.num == 0 => {
+                media.push(MediaChange::Removed(index))
+            },
+            (Some(o), Some(n)) if o.media.port.num == 0 && n.media.port.num != 0 => {
+                media.push(MediaChange::Added(index))
+            },
+            (Some(o), Some(n)) => if let Some(d) = media_diff(index, old, o, new, n) {
+                media.push(MediaChange::Changed(d))
+            },
+            (Some(_), None) => media.push(MediaChange::Removed(index)),
+            (None, Some(_)) => media.push(MediaChange::Added(index)),
+            (None, None) => ()
+        }
+    }
+
+    Diff {
+        media
+    }
+}
+
+fn media_diff(
+    index: usize,
+    old_session: &Sdp,
+    old: &MediaDescription,
+    new_session: &Sdp,
+    new: &MediaDescription
+) -> Option<MediaDiff> {
+    let mut diff = MediaDiff {
+        index,
+        ..Default::default()
+    };
+
+    for (pt, value) in &new.attributes.rtpmap {
+        match old.attributes.rtpmap.get(pt) {
+            Some(v) if v != value || format(old, *pt) != format(new, *pt) => {
+                diff.changed_codecs.push(*pt)
+            },
+            None => diff.added_codecs.push(*pt),
+            _ => ()
+        }
+    }
+
+    for pt in old.attributes.rtpmap.keys() {
+        if !new.attributes.rtpmap.contains_key(pt) {
+            diff.removed_codecs.push(*pt);
+        }
+    }
+
+    diff.added_codecs.sort_unstable();
+    diff.removed_codecs.sort_unstable();
+    diff.changed_codecs.sort_unstable();
+
+    let old_direction = direction(old_session, old);
+    let new_direction = direction(new_session, new);
+    if old_direction != new_direction {
+        diff.direction = Some((old_direction, new_direction));
+    }
+
+    diff.ice_restart = ice_credentials(old_session, old)
+        != ice_credentials(new_session, new);
+    diff.tls_id_changed = old.attributes.tls_id != new.attributes.tls_id;
+    if diff == (MediaDiff { index, ..Default::default() }) {
+        return None
+    }
+
+    Some(diff)
+}
+
+/// the format parameters and the feedback messages of a payload type,
+/// sorted, their order is not significant.
+fn format(media: &MediaDescription, pt: u8) -> (Vec<String>, Vec<String>) {
+    let mut params = media.attributes.fmtp
+        .get(&pt)
+        .map(|p| p.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<String>>())
+        .unwrap_or_default();
+    let mut feedback = media.attributes.rtcp_fb
+        .iter()
+        .filter(|fb| fb.pt == Some(pt))
+        .map(|fb| fb.to_string())
+        .collect::<Vec<String>>();
+    params.sort();
+    feedback.sort();
+    (params, feedback)
+}
+
+/// the media-level direction overrides the session-level one.
+fn direction(session: &Sdp, media: &MediaDescription) -> Direction {
+    media.attributes.direction()
+        .or_else(|| session.attributes.direction())
+        .unwrap_or_default()
+}
+
+/// the media-level credentials override the session-level ones.
+fn ice_credentials<'a>(
+    session: &Sdp<'a>,
+    media: &MediaDescription<'a>
+) -> (Option<&'a str>, Option<&'a str>) {
+    (
+        media.attributes.ice_ufrag.or(session.attributes.ice_ufrag),
+        media.attributes.ice_pwd.or(session.attributes.ice_pwd)
+    )
+}
+
+/// compare two session descriptions, ignoring the presentation.
+///
+/// the descriptions are parsed and compared through the parsed values,
+/// they are equal when they only differ in the order of the "a=" lines
+/// of a section, the line endings, the whitespace between fields, or
+/// the case of the encoding names of "a=rtpmap:".  the order of the
+/// media sections and of their formats is significant, a description
+/// that fails to parse is not equal to any other.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::diff::eq_semantic;
+///
+/// let a = "v=0\r\n\
+///     c=IN IP4 192.168.1.10\r\n\
+///     a=ice-ufrag:F7gI\r\n\
+///     a=fingerprint:sha-256 49:66:12:17:0D:1C\r\n\
+///     m=audio 9 RTP/AVP 111 0\r\n\
+///     a=rtpmap:111 opus/48000/2\r\n\
+///     a=mid:0\r\n";
+///
+/// let b = "v=0\n\
+///     c=IN  IP4 192.168.1.10\n\
+///     a=fingerprint:sha-256 49:66:12:17:0D:1C \n\
+///     a=ice-ufrag:F7gI\n\
+///     m=audio 9 RTP/AVP 111 0\n\
+///     a=mid:0\n\
+///     a=rtpmap:111 OPUS/48000/2\n";
+///
+/// assert!(eq_semantic(a, b));
+/// assert!(!eq_semantic(a, &a.replace("111 0", "0 111")));
+/// assert!(!eq_semantic(a, &a.replace("F7gI", "f7gi")));
+/// assert!(!eq_semantic(a, &a.replace("opus", "foo")));
+/// ```
+pub fn eq_semantic(a: &str, b: &str) -> bool {
+    match (canonical(a), canonical(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false
+    }
+}
+
+/// the sections of a description written from its parsed values, with
+/// the "a=" lines of each section sorted.
+fn canonical(value: &str) -> Option<Vec<Vec<String>>> {
+    let text = value
+        .lines()
+        .map(|l| l.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<String>>()
+        .join("\r\n");
+    let sdp = Sdp::try_from(text.as_str()).ok()?;
+    let mut sections: Vec<Vec<String>> = vec![Vec::new()];
+    for line in sdp.to_string().lines() {
+        if line.starts_with("m=") {
+            sections.push(Vec::new());
+        }
+
+        let index = sections.len() - 1;
+        let attributes = match index {
+            0 => &sdp.attributes,
+            i => &sdp.media.get(i - 1)?.attributes
+        };
+
+        sections[index].push(match line.strip_prefix("a=") {
+            Some(a) => format!("a={}", canonical_attribute(attributes, a)),
+            None => line.to_string()
+        });
+    }
+
+    for section in &mut sections {
+        let start = section
+            .iter()
+            .position(|l| l.starts_with("a="))
+            .unwrap_or(section.len());
+        section[start..].sort();
+    }
+
+    Some(sections)
+}
+
+/// an "a=rtpmap:" line is written from its parsed value, which folds
+/// the case of the encoding name.
+fn canonical_attribute(attributes: &Attributes, line: &str) -> String {
+    let attribute = Attribute::from(line);
+    match (attribute.key(), attribute.format()) {
+        (Some(Key::RtpMap), Some(pt)) => match attributes.rtpmap.get(&pt) {
+            Some(value) => format!("{}:{} {}", Key::RtpMap, pt, value),
+            None => line.to_string()
+        },
+        _ => line.to_string()
+    }
+}
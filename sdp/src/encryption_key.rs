@@ -0,0 +1,92 @@
+use anyhow::{
+    ensure,
+    anyhow
+};
+
+use std::{
+    convert::TryFrom,
+    fmt
+};
+
+/// Encryption Keys ("k=")
+///
+/// k=<method>
+/// k=<method>:<encryption key>
+///
+/// The "k=" line (key-field) is obsolete and MUST NOT be used, it is
+/// included in [RFC8866](https://datatracker.ietf.org/doc/html/rfc8866#section-5.12)
+/// for legacy reasons only, and is only parsed for compatibility with
+/// RTSP and SIP descriptions still carrying it.
+///
+/// * clear:  the encryption key is included untransformed.
+/// * base64:  the encryption key is included, base64 encoded.
+/// * uri:  a URI is included in the key field, to obtain the key.
+/// * prompt:  no key is included, the user is prompted for the key.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EncryptionKey<'a> {
+    Clear(&'a str),
+    Base64(&'a str),
+    Uri(&'a str),
+    Prompt
+}
+
+impl<'a> fmt::Display for EncryptionKey<'a> {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::encryption_key::*;
+    ///
+    /// assert_eq!(format!("{}", EncryptionKey::Clear("secret")), "clear:secret");
+    /// assert_eq!(format!("{}", EncryptionKey::Base64("c2VjcmV0")), "base64:c2VjcmV0");
+    /// assert_eq!(format!("{}", EncryptionKey::Uri("https://example.com/key")), "uri:https://example.com/key");
+    /// assert_eq!(format!("{}", EncryptionKey::Prompt), "prompt");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Clear(k) =>   write!(f, "clear:{}", k),
+            Self::Base64(k) =>  write!(f, "base64:{}", k),
+            Self::Uri(k) =>     write!(f, "uri:{}", k),
+            Self::Prompt =>     write!(f, "prompt")
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for EncryptionKey<'a> {
+    type Error = anyhow::Error;
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::encryption_key::*;
+    /// use std::convert::*;
+    ///
+    /// assert_eq!(EncryptionKey::try_from("clear:secret").unwrap(), EncryptionKey::Clear("secret"));
+    /// assert_eq!(EncryptionKey::try_from("base64:c2VjcmV0").unwrap(), EncryptionKey::Base64("c2VjcmV0"));
+    /// assert_eq!(EncryptionKey::try_from("uri:https://example.com/key").unwrap(), EncryptionKey::Uri("https://example.com/key"));
+    /// assert_eq!(EncryptionKey::try_from("prompt").unwrap(), EncryptionKey::Prompt);
+    ///
+    /// assert!(EncryptionKey::try_from("clear").is_err());
+    /// assert!(EncryptionKey::try_from("prompt:secret").is_err());
+    /// assert!(EncryptionKey::try_from("rsa:secret").is_err());
+    /// ```
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (method, key) = match value.split_once(':') {
+            Some((m, k)) => (m, Some(k)),
+            None => (value, None)
+        };
+
+        if method == "prompt" {
+            ensure!(key.is_none(), "invalid encryption key!");
+            return Ok(Self::Prompt)
+        }
+
+        let key = key
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| anyhow!("invalid encryption key!"))?;
+        match method {
+            "clear" =>  Ok(Self::Clear(key)),
+            "base64" => Ok(Self::Base64(key)),
+            "uri" =>    Ok(Self::Uri(key)),
+            _ => Err(anyhow!("invalid encryption key!"))
+        }
+    }
+}
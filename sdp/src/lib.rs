@@ -2,20 +2,39 @@ pub mod attributes;
 pub mod repeat_times;
 pub mod time_zones;
 pub mod connection;
+pub mod encryption_key;
 pub mod bandwidth;
 pub mod origin;
 pub mod timing;
 pub mod media;
+pub mod diff;
+pub mod validate;
+pub mod plan;
+pub mod limits;
+pub mod writer;
+pub mod redact;
 pub mod util;
 
 use repeat_times::RepeatTimes;
-use attributes::Attributes;
+use attributes::{
+    Attributes,
+    Duplicate,
+    Scoping
+};
 use connection::Connection;
+use encryption_key::EncryptionKey;
 use time_zones::TimeZones;
-use bandwidth::Bandwidth;
+use bandwidth::Bandwidths;
 use timing::Timing;
 use origin::Origin;
-use media::Media;
+use limits::{
+    Counter,
+    Limits
+};
+use media::{
+    MediaDescription,
+    Media
+};
 use anyhow::{
     ensure,
     anyhow
@@ -37,6 +56,7 @@ pub enum Key {
     Phone,
     Connection,
     Bandwidth,
+    EncryptionKey,
     Timing,
     RepeatTimes,
     TimeZones,
@@ -60,6 +80,17 @@ pub enum AddrKind {
     IP6,
 }
 
+/// parsing options.
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+    /// the policy for repeated "a=fmtp:" and "a=rtpmap:" attributes.
+    pub duplicate: Duplicate,
+    /// the resource limits.
+    pub limits: Limits,
+    /// the policy for attributes at the wrong usage level.
+    pub scoping: Scoping,
+}
+
 /// SDP: Session Description Protocol
 ///
 /// An SDP description is denoted by the media type "application/sdp"
@@ -149,49 +180,255 @@ pub struct Sdp<'a> {
     /// Connection Information ("c=")
     pub connection: Option<Connection>,
     /// Bandwidth ("b=")
-    pub bandwidth: Option<Bandwidth>,
+    pub bandwidth: Bandwidths,
+    /// Encryption Keys ("k=")
+    /// Obsolete, only parsed for legacy descriptions.
+    pub encryption_key: Option<EncryptionKey<'a>>,
     /// Timing ("t=")
     pub timing: Option<Timing>,
     /// Repeat Times ("r=")
-    pub repeat_times: Option<RepeatTimes>,
+    /// Zero or more "r=" lines may follow the "t=" line.
+    pub repeat_times: Vec<RepeatTimes>,
     /// Time Zones ("z=")
     pub time_zones: Option<TimeZones>,
     /// Attributes ("a=")
     pub attributes: Attributes<'a>,
-    /// Media ("m=")
-    pub media: Option<Media>,
+    /// Media Descriptions ("m=")
+    pub media: Vec<MediaDescription<'a>>,
 }
 
 impl<'a> Sdp<'a> {
+    /// handle a line of the session description.
+    ///
+    /// the "u=", "e=" and "p=" lines are session-level fields, they are
+    /// rejected after the first "m=" line.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::*;
+    /// use std::convert::*;
+    ///
+    /// let sdp = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     s=-\r\n\
+    ///     u=http://www.example.com/seminars/sdp.pdf\r\n\
+    ///     e=j.doe@example.com (Jane Doe)\r\n\
+    ///     p=+1 617 555-6011\r\n"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(sdp.uri, Some("http://www.example.com/seminars/sdp.pdf"));
+    /// assert_eq!(sdp.email, Some("j.doe@example.com (Jane Doe)"));
+    /// assert_eq!(sdp.phone, Some("+1 617 555-6011"));
+    ///
+    /// let mut sdp = Sdp::default();
+    /// sdp.handle_line(Key::Media, "audio 9 RTP/AVP 0").unwrap();
+    /// assert!(sdp.handle_line(Key::Uri, "http://www.example.com").is_err());
+    /// assert!(sdp.handle_line(Key::Email, "j.doe@example.com").is_err());
+    /// assert!(sdp.handle_line(Key::Phone, "+1 617 555-6011").is_err());
+    /// ```
     pub fn handle_line(&mut self, key: Key, data: &'a str) -> anyhow::Result<()> {
         Ok(match key {
             Key::Origin => self.origin = Some(Origin::try_from(data)?),
             Key::SessionName => self.session_name = util::placeholder(data),
-            Key::SessionInfo => self.session_info = util::placeholder(data),
-            Key::Uri => self.uri = util::placeholder(data),
-            Key::Email => self.email = util::placeholder(data),
-            Key::Phone => self.phone = util::placeholder(data),
-            Key::Connection => self.connection = Some(Connection::try_from(data)?),
-            Key::Bandwidth => self.bandwidth = Some(Bandwidth::try_from(data)?),
+            Key::SessionInfo => self.handle_info(data),
+            Key::Uri => self.uri = self.session_field(data)?,
+            Key::Email => self.email = self.session_field(data)?,
+            Key::Phone => self.phone = self.session_field(data)?,
+            Key::Connection => self.handle_connection(data)?,
+            Key::Bandwidth => self.handle_bandwidth(data)?,
+            Key::EncryptionKey => self.handle_encryption_key(data)?,
             Key::Timing => self.timing = Some(Timing::try_from(data)?),
-            Key::RepeatTimes => self.repeat_times = Some(RepeatTimes::try_from(data)?),
+            Key::RepeatTimes => self.repeat_times.push(RepeatTimes::try_from(data)?),
             Key::TimeZones => self.time_zones = Some(TimeZones::try_from(data)?),
-            Key::Attributes => self.attributes.handle(data)?,
-            Key::Media => self.media = Some(Media::try_from(data)?),
+            Key::Attributes => self.handle_attributes(data)?,
+            Key::Media => self.handle_media(data)?,
         })
     }
+
+    /// a field only allowed before the media descriptions.
+    fn session_field(&self, data: &'a str) -> anyhow::Result<Option<&'a str>> {
+        ensure!(self.media.is_empty(), "invalid session field!");
+        Ok(util::placeholder(data))
+    }
+
+    /// the media inherits the session parsing policies.
+    fn handle_media(&mut self, data: &'a str) -> anyhow::Result<()> {
+        let mut media: MediaDescription = Media::try_from(data)?.into();
+        media.attributes.duplicate = self.attributes.duplicate;
+        media.attributes.scoping = self.attributes.scoping;
+        self.media.push(media);
+        Ok(())
+    }
+
+    /// the attributes of the current media, or of the session.
+    fn current_attributes(&self) -> &Attributes<'a> {
+        match self.media.last() {
+            Some(media) => &media.attributes,
+            None => &self.attributes
+        }
+    }
+
+    /// the connection after "m=" belongs to the current media.
+    fn handle_connection(&mut self, data: &'a str) -> anyhow::Result<()> {
+        let connection = Some(Connection::try_from(data)?);
+        match self.media.last_mut() {
+            Some(media) => media.connection = connection,
+            None => self.connection = connection
+        }
+
+        Ok(())
+    }
+
+    /// the information after "m=" is the title of the current media.
+    fn handle_info(&mut self, data: &'a str) {
+        let info = util::placeholder(data);
+        match self.media.last_mut() {
+            Some(media) => media.info = info,
+            None => self.session_info = info
+        }
+    }
+
+    /// the bandwidth after "m=" belongs to the current media.
+    fn handle_bandwidth(&mut self, data: &'a str) -> anyhow::Result<()> {
+        match self.media.last_mut() {
+            Some(media) => media.bandwidth.handle(data),
+            None => self.bandwidth.handle(data)
+        }
+    }
+
+    /// the encryption key after "m=" belongs to the current media.
+    fn handle_encryption_key(&mut self, data: &'a str) -> anyhow::Result<()> {
+        let key = Some(EncryptionKey::try_from(data)?);
+        match self.media.last_mut() {
+            Some(media) => media.encryption_key = key,
+            None => self.encryption_key = key
+        }
+
+        Ok(())
+    }
+
+    /// the attributes after "m=" belongs to the current media.
+    fn handle_attributes(&mut self, data: &'a str) -> anyhow::Result<()> {
+        match self.media.last_mut() {
+            Some(media) => media.attributes.handle(data),
+            None => self.attributes.handle(data)
+        }
+    }
 }
 
 impl<'a> TryFrom<&'a str> for Sdp<'a> {
     type Error = anyhow::Error;
-    #[rustfmt::skip]
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::Sdp;
+    /// use sdp::encryption_key::EncryptionKey;
+    /// use std::convert::*;
+    ///
+    /// let sdp = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     o=- 0 0 IN IP4 127.0.0.1\r\n\
+    ///     s=-\r\n\
+    ///     c=IN IP4 127.0.0.1\r\n\
+    ///     b=CT:1024\r\n\
+    ///     t=3034423619 3042462419\r\n\
+    ///     r=7d 1h 0 25h\r\n\
+    ///     r=7d 1h 3h\r\n\
+    ///     z=2882844526 -1h 2898848070 0\r\n\
+    ///     k=prompt\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     c=IN IP4 0.0.0.0\r\n\
+    ///     b=AS:64\r\n\
+    ///     b=TIAS:64000\r\n\
+    ///     k=clear:secret\r\n\
+    ///     a=mid:0\r\n\
+    ///     a=candidate:1 1 udp 2130706431 2001:db8::1 8998 typ host\r\n\
+    ///     a=end-of-candidates\r\n\
+    ///     m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+    ///     a=mid:1\r\n"
+    /// ).unwrap();
+    ///
+    /// assert!(sdp.connection.is_some());
+    /// assert_eq!(sdp.bandwidth.ct, Some(1024));
+    /// assert_eq!(sdp.bandwidth.r#as, None);
+    /// assert_eq!(sdp.encryption_key, Some(EncryptionKey::Prompt));
+    /// assert_eq!(sdp.media[0].encryption_key, Some(EncryptionKey::Clear("secret")));
+    /// assert_eq!(sdp.repeat_times.len(), 2);
+    /// assert_eq!(sdp.repeat_times[0].offsets_from_start_time, vec![0.0, 90000.0]);
+    /// assert_eq!(sdp.repeat_times[1].offsets_from_start_time, vec![10800.0]);
+    /// assert_eq!(sdp.time_zones.unwrap().get_values().len(), 2);
+    /// assert_eq!(sdp.media.len(), 2);
+    /// assert!(sdp.media[0].connection.is_some());
+    /// assert_eq!(sdp.media[0].bandwidth.r#as, Some(64));
+    /// assert_eq!(sdp.media[0].bandwidth.tias, Some(64000));
+    /// assert_eq!(sdp.media[0].attributes.candidates.len(), 1);
+    /// assert_eq!(sdp.media[0].attributes.candidates[0].address, "2001:db8::1");
+    /// assert!(sdp.media[0].attributes.end_of_candidates);
+    /// assert!(sdp.media[1].connection.is_none());
+    /// assert!(sdp.media[1].attributes.candidates.is_empty());
+    /// assert!(!sdp.media[1].attributes.end_of_candidates);
+    /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value, &Options::default())
+    }
+}
+
+impl<'a> Sdp<'a> {
+    /// parse with options.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::{Duplicate, Scoping};
+    /// use sdp::{Sdp, Options};
+    /// use std::convert::*;
+    ///
+    /// let value = "v=0\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     a=rtpmap:111 opus/48000/2\r\n\
+    ///     a=rtpmap:111 opus/48000/2\r\n";
+    ///
+    /// assert!(Sdp::try_from(value).is_ok());
+    /// let options = Options {
+    ///     duplicate: Duplicate::Error,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Sdp::parse(value, &options).is_err());
+    ///
+    /// let value = "v=0\r\n\
+    ///     a=ptime:20\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     a=tool:foobar V3.2\r\n";
+    ///
+    /// let sdp = Sdp::try_from(value).unwrap();
+    /// assert_eq!(sdp.attributes.misplaced, vec!["ptime:20"]);
+    /// assert_eq!(sdp.media[0].attributes.misplaced, vec!["tool:foobar V3.2"]);
+    ///
+    /// let options = Options {
+    ///     scoping: Scoping::Error,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Sdp::parse(value, &options).is_err());
+    /// ```
+    #[rustfmt::skip]
+    pub fn parse(value: &'a str, options: &Options) -> anyhow::Result<Self> {
         let mut sdp = Self::default();
+        sdp.attributes.duplicate = options.duplicate;
+        sdp.attributes.scoping = options.scoping;
+        let mut counter = Counter::default();
         for line in value.lines() {
-            if !line.is_empty() {
-                let (key, data) = line.split_at(2);
+            options.limits.check_line(line)?;
+            if let (Some(key), Some(data)) = (line.get(..2), line.get(2..)) {
                 if let Ok(k) = Key::try_from(key) {
+                    counter.count(&k, &options.limits)?;
+                    let is_attribute = k == Key::Attributes;
                     sdp.handle_line(k, data)?;
+                    if is_attribute {
+                        options.limits.check_fmtp(sdp.current_attributes(), data)?;
+                    }
                 }   
             }
         }
@@ -289,6 +526,7 @@ impl fmt::Display for Key {
             Self::Phone =>           "p=",
             Self::Connection =>      "c=",
             Self::Bandwidth =>       "b=",
+            Self::EncryptionKey =>   "k=",
             Self::Timing =>          "t=",
             Self::RepeatTimes =>     "r=",
             Self::TimeZones =>       "z=",
@@ -326,6 +564,7 @@ impl<'a> TryFrom<&'a str> for Key {
             "p=" => Ok(Self::Phone),
             "c=" => Ok(Self::Connection),
             "b=" => Ok(Self::Bandwidth),
+            "k=" => Ok(Self::EncryptionKey),
             "t=" => Ok(Self::Timing),
             "r=" => Ok(Self::RepeatTimes),
             "z=" => Ok(Self::TimeZones),
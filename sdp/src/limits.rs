@@ -0,0 +1,193 @@
+use super::attributes::{
+    Attribute,
+    Attributes
+};
+
+use super::Key;
+use std::fmt;
+
+/// parser resource limits.
+///
+/// a session description received over signaling is untrusted, the
+/// limits bound the memory the parser allocates for it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Limits {
+    /// the maximum length of a line, in bytes.
+    pub max_line_length: usize,
+    /// the maximum number of "a=" lines of the session or of a media
+    /// description.
+    pub max_attributes: usize,
+    /// the maximum number of parameters of an "a=fmtp:" format.
+    pub max_fmtp_params: usize,
+    /// the maximum number of media descriptions.
+    pub max_media: usize,
+    /// the maximum number of "r=" lines.
+    pub max_repeat_times: usize,
+    /// the maximum number of "b=" lines of the session or of a media
+    /// description.
+    pub max_bandwidths: usize,
+}
+
+/// limit exceeded.
+///
+/// the parser returns it through `anyhow::Error`, use `downcast_ref`
+/// to tell it from a malformed description.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::limits::*;
+/// use sdp::{Sdp, Options};
+///
+/// let options = Options {
+///     limits: Limits {
+///         max_line_length: 64,
+///         max_attributes: 2,
+///         max_fmtp_params: 2,
+///         max_media: 2,
+///         max_repeat_times: 1,
+///         max_bandwidths: 1,
+///     },
+///     ..Default::default()
+/// };
+///
+/// let error = Sdp::parse("v=0\r\na=tool:foobar V3.2 V3.2 V3.2 V3.2 V3.2 V3.2 V3.2 V3.2 V3.2 V3.2 V3.2 V3.2\r\n", &options).unwrap_err();
+/// assert_eq!(error.downcast_ref::<LimitError>(), Some(&LimitError::LineLength(64)));
+///
+/// let error = Sdp::parse("v=0\r\na=sendrecv\r\na=rtcp-mux\r\na=ice-lite\r\n", &options).unwrap_err();
+/// assert_eq!(error.downcast_ref::<LimitError>(), Some(&LimitError::Attributes(2)));
+///
+/// let error = Sdp::parse("v=0\r\nm=video 9 RTP/AVP 96\r\na=fmtp:96 a=1;b=2;c=3\r\n", &options).unwrap_err();
+/// assert_eq!(error.downcast_ref::<LimitError>(), Some(&LimitError::FmtpParams(2)));
+///
+/// assert!(Sdp::parse("v=0\r\nm=video 9 RTP/AVP 96\r\na=fmtp:96 a=1;b=2\r\n", &options).is_ok());
+///
+/// let error = Sdp::parse("v=0\r\nm=audio 9 RTP/AVP 0\r\nm=audio 9 RTP/AVP 0\r\nm=audio 9 RTP/AVP 0\r\n", &options).unwrap_err();
+/// assert_eq!(error.downcast_ref::<LimitError>(), Some(&LimitError::Media(2)));
+///
+/// let error = Sdp::parse("v=0\r\nt=0 0\r\nr=7d 1h 0\r\nr=7d 1h 0\r\n", &options).unwrap_err();
+/// assert_eq!(error.downcast_ref::<LimitError>(), Some(&LimitError::RepeatTimes(1)));
+///
+/// let error = Sdp::parse("v=0\r\nm=audio 9 RTP/AVP 0\r\nb=AS:64\r\nb=AS:64\r\n", &options).unwrap_err();
+/// assert_eq!(error.downcast_ref::<LimitError>(), Some(&LimitError::Bandwidths(1)));
+///
+/// // the attributes and the bandwidths are counted per section.
+/// assert!(Sdp::parse("v=0\r\nb=AS:64\r\na=sendrecv\r\na=rtcp-mux\r\nm=audio 9 RTP/AVP 0\r\nb=AS:64\r\na=sendrecv\r\na=rtcp-mux\r\n", &options).is_ok());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LimitError {
+    LineLength(usize),
+    Attributes(usize),
+    FmtpParams(usize),
+    Media(usize),
+    RepeatTimes(usize),
+    Bandwidths(usize),
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_line_length: 4096,
+            max_attributes: 1024,
+            max_fmtp_params: 64,
+            max_media: 256,
+            max_repeat_times: 16,
+            max_bandwidths: 16,
+        }
+    }
+}
+
+impl Limits {
+    /// check a line.
+    pub fn check_line(&self, line: &str) -> Result<(), LimitError> {
+        if line.len() > self.max_line_length {
+            return Err(LimitError::LineLength(self.max_line_length))
+        }
+
+        Ok(())
+    }
+
+    /// check the parameters of an "a=fmtp:" line once handled.
+    ///
+    /// only the format of the line is checked, so that the whole
+    /// description is not scanned again after each line.
+    pub fn check_fmtp(&self, attributes: &Attributes, line: &str) -> Result<(), LimitError> {
+        let params = Attribute::from(line)
+            .format()
+            .and_then(|pt| attributes.fmtp.get(&pt))
+            .map(|p| p.len())
+            .unwrap_or(0);
+        if params > self.max_fmtp_params {
+            return Err(LimitError::FmtpParams(self.max_fmtp_params))
+        }
+
+        Ok(())
+    }
+}
+
+/// line counter.
+///
+/// counts the lines of a description against the limits before they
+/// are handled, the "b=" and "a=" lines are counted per section.
+#[derive(Debug, Default)]
+pub struct Counter {
+    media: usize,
+    repeat_times: usize,
+    bandwidths: usize,
+    attributes: usize,
+}
+
+impl Counter {
+    /// count a line.
+    pub fn count(&mut self, key: &Key, limits: &Limits) -> Result<(), LimitError> {
+        let (count, max, error): (usize, usize, fn(usize) -> LimitError) = match key {
+            Key::Media => {
+                self.bandwidths = 0;
+                self.attributes = 0;
+                self.media += 1;
+                (self.media, limits.max_media, LimitError::Media)
+            },
+            Key::RepeatTimes => {
+                self.repeat_times += 1;
+                (self.repeat_times, limits.max_repeat_times, LimitError::RepeatTimes)
+            },
+            Key::Bandwidth => {
+                self.bandwidths += 1;
+                (self.bandwidths, limits.max_bandwidths, LimitError::Bandwidths)
+            },
+            Key::Attributes => {
+                self.attributes += 1;
+                (self.attributes, limits.max_attributes, LimitError::Attributes)
+            },
+            _ => return Ok(())
+        };
+
+        if count > max {
+            return Err(error(max))
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for LimitError {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::limits::*;
+    ///
+    /// assert_eq!(format!("{}", LimitError::LineLength(4096)), "line length limit of 4096 exceeded!");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LineLength(n) => write!(f, "line length limit of {} exceeded!", n),
+            Self::Attributes(n) => write!(f, "attributes limit of {} exceeded!", n),
+            Self::FmtpParams(n) => write!(f, "fmtp params limit of {} exceeded!", n),
+            Self::Media(n) => write!(f, "media limit of {} exceeded!", n),
+            Self::RepeatTimes(n) => write!(f, "repeat times limit of {} exceeded!", n),
+            Self::Bandwidths(n) => write!(f, "bandwidths limit of {} exceeded!", n),
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
@@ -1,9 +1,26 @@
+use super::attributes::{
+    Attribute,
+    Attributes,
+    Capability,
+    Codec,
+    Key,
+    Level,
+    intersect
+};
+
+use super::connection::Connection;
+use super::bandwidth::Bandwidths;
+use super::encryption_key::EncryptionKey;
+use super::util::next_field;
 use anyhow::{
+    Result,
     ensure,
     anyhow
 };
 
 use std::{
+    borrow::Cow,
+    collections::HashMap,
     convert::TryFrom,
     fmt
 };
@@ -48,6 +65,11 @@ pub enum Encoding {
 /// Modulation (PCM) audio and RTP PCM audio; another might be TCP/RTP
 /// PCM audio.  In addition, relays and monitoring tools that are
 /// transport-protocol-specific but format-independent are possible.
+/// 
+/// *  TCP: denotes an unspecified protocol running over TCP,
+///    [RFC4145](https://datatracker.ietf.org/doc/html/rfc4145#section-3),
+///    such as "TCP/RTP/AVP", or "TCP/BFCP" for the Binary Floor Control
+///    Protocol.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Proto {
     Udp,
@@ -55,7 +77,10 @@ pub enum Proto {
     Rtp,
     Avp,
     Savp,
-    Savpf
+    Savpf,
+    Tcp,
+    Avpf,
+    Bfcp
 }
 
 /// media port.
@@ -155,9 +180,325 @@ pub struct Media {
     /// For media using other transport protocols, the <fmt> field is
     /// protocol specific.  Rules for interpretation of the <fmt> sub-
     /// field MUST be defined when registering new protocols.
+    ///
+    /// The "*" wildcard of non-RTP protocols, such as "TCP/BFCP", is
+    /// not a format, and leaves the list empty.
     pub fmts: Vec<u8>
 }
 
+/// Media Description
+///
+/// A media description starts with an "m=" line and collects all the
+/// lines following it, until the next "m=" line or the end of the
+/// session description.  Media-level "i=", "c=", "b=" and "a=" lines override
+/// the session-level ones for this media only.
+#[derive(Debug)]
+pub struct MediaDescription<'a> {
+    /// Media ("m=")
+    pub media: Media,
+    /// Media Title ("i=")
+    /// labels the media stream, the session-level "i=" line applies
+    /// when it is absent.
+    pub info: Option<&'a str>,
+    /// Connection Information ("c=")
+    pub connection: Option<Connection>,
+    /// Bandwidth ("b=")
+    pub bandwidth: Bandwidths,
+    /// Encryption Keys ("k=")
+    pub encryption_key: Option<EncryptionKey<'a>>,
+    /// Attributes ("a=")
+    pub attributes: Attributes<'a>,
+}
+
+impl<'a> From<Media> for MediaDescription<'a> {
+    fn from(media: Media) -> Self {
+        Self {
+            media,
+            info: None,
+            connection: None,
+            bandwidth: Bandwidths::default(),
+            encryption_key: None,
+            attributes: Attributes {
+                level: Level::Media,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl<'a> MediaDescription<'a> {
+    /// get the codec capabilities.
+    ///
+    /// in the order of the "m=" line, which is the order of preference,
+    /// formats without "a=rtpmap:" are skipped.
+    pub fn capabilities(&self) -> Vec<Capability<'a>> {
+        self.media.fmts
+            .iter()
+            .filter_map(|pt| self.attributes.capability(*pt))
+            .collect()
+    }
+
+    /// intersect with the remote media description.
+    ///
+    /// returns the pairs of compatible local and remote payload types,
+    /// in the local order of preference.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let local = Sdp::try_from(
+    ///     "m=audio 9 UDP/TLS/RTP/SAVPF 111 0 8\r\n\
+    ///     a=rtpmap:111 opus/48000/2\r\n\
+    ///     a=rtpmap:0 PCMU/8000\r\n\
+    ///     a=rtpmap:8 PCMA/8000\r\n"
+    /// ).unwrap();
+    ///
+    /// let remote = Sdp::try_from(
+    ///     "m=audio 9 UDP/TLS/RTP/SAVPF 8 109\r\n\
+    ///     a=rtpmap:8 PCMA/8000\r\n\
+    ///     a=rtpmap:109 opus/48000/2\r\n"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(local.media[0].intersect(&remote.media[0]), vec![(111, 109), (8, 8)]);
+    /// ```
+    pub fn intersect(&self, remote: &MediaDescription) -> Vec<(u8, u8)> {
+        intersect(&self.capabilities(), &remote.capabilities())
+    }
+
+    /// remove a codec.
+    ///
+    /// removes every payload type of the codec, and the retransmission
+    /// payload types associated with them, from the "m=" line and their
+    /// "a=rtpmap:", "a=fmtp:", "a=rtcp-fb:" and "a=framesize:" attributes.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::Codec;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let mut sdp = Sdp::try_from(
+    ///     "m=video 9 UDP/TLS/RTP/SAVPF 96 97 98\r\n\
+    ///     a=rtpmap:96 VP8/90000\r\n\
+    ///     a=rtcp-fb:96 nack pli\r\n\
+    ///     a=framesize:96 320-240\r\n\
+    ///     a=rtpmap:97 rtx/90000\r\n\
+    ///     a=fmtp:97 apt=96\r\n\
+    ///     a=rtpmap:98 H264/90000\r\n\
+    ///     a=rtcp-fb:* transport-cc\r\n"
+    /// ).unwrap();
+    ///
+    /// let media = &mut sdp.media[0];
+    /// media.remove_codec(Codec::Vp8);
+    ///
+    /// assert_eq!(media.media.fmts, vec![98]);
+    /// assert_eq!(media.attributes.rtpmap.len(), 1);
+    /// assert!(media.attributes.fmtp.is_empty());
+    /// assert!(media.attributes.framesize.is_empty());
+    /// assert_eq!(media.attributes.rtcp_fb.len(), 1);
+    ///
+    /// let names = media.attributes.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["rtpmap:98 H264/90000", "rtcp-fb:* transport-cc"]);
+    /// ```
+    pub fn remove_codec(&mut self, codec: Codec) {
+        let attributes = &mut self.attributes;
+        let mut pts = attributes.rtpmap
+            .iter()
+            .filter(|(_, v)| v.codec == codec)
+            .map(|(pt, _)| *pt)
+            .collect::<Vec<u8>>();
+        let rtx = attributes.rtpmap
+            .iter()
+            .filter(|(_, v)| v.codec == Codec::Rtx)
+            .filter(|(pt, _)| {
+                attributes.fmtp
+                    .get(pt)
+                    .and_then(|p| p.get("apt"))
+                    .and_then(|apt| apt.parse::<u8>().ok())
+                    .map(|apt| pts.contains(&apt))
+                    .unwrap_or(false)
+            })
+            .map(|(pt, _)| *pt)
+            .collect::<Vec<u8>>();
+        pts.extend(rtx);
+
+        self.media.fmts.retain(|pt| !pts.contains(pt));
+        attributes.rtpmap.retain(|pt, _| !pts.contains(pt));
+        attributes.fmtp.retain(|pt, _| !pts.contains(pt));
+        attributes.framesize.retain(|pt, _| !pts.contains(pt));
+        attributes.rtcp_fb.retain(|fb| match fb.pt {
+            Some(pt) => !pts.contains(&pt),
+            None => true
+        });
+        attributes.lines.retain(|line| match Attribute::from(line.as_ref()).format() {
+            Some(pt) => !pts.contains(&pt),
+            None => true
+        });
+    }
+
+    /// prefer a codec.
+    ///
+    /// moves the payload types of the codec to the front of the "m=" 
+    /// line, keeping the relative order of all the other formats.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::attributes::Codec;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let mut sdp = Sdp::try_from(
+    ///     "m=video 9 UDP/TLS/RTP/SAVPF 96 97 98\r\n\
+    ///     a=rtpmap:96 VP8/90000\r\n\
+    ///     a=rtpmap:97 rtx/90000\r\n\
+    ///     a=rtpmap:98 H264/90000\r\n"
+    /// ).unwrap();
+    ///
+    /// sdp.media[0].prefer_codec(Codec::H264);
+    /// assert_eq!(sdp.media[0].media.fmts, vec![98, 96, 97]);
+    /// ```
+    pub fn prefer_codec(&mut self, codec: Codec) {
+        let rtpmap = &self.attributes.rtpmap;
+        let is_codec = |pt: &u8| rtpmap.get(pt).map(|v| v.codec == codec).unwrap_or(false);
+        let (mut preferred, others): (Vec<u8>, Vec<u8>) = self.media.fmts
+            .iter()
+            .partition(|pt| is_codec(pt));
+        preferred.extend(others);
+        self.media.fmts = preferred;
+    }
+
+    /// renumber payload types.
+    ///
+    /// applies all the mappings at once to the "m=" line, the 
+    /// "a=rtpmap:", "a=fmtp:", "a=rtcp-fb:" and "a=framesize:" 
+    /// attributes and their lines, and the "apt" parameters of the
+    /// retransmission formats, so swapping two payload types is possible.  payload types
+    /// missing from the mappings keep their number, the result must not
+    /// have two formats with the same payload type.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut sdp = Sdp::try_from(
+    ///     "m=video 9 UDP/TLS/RTP/SAVPF 96 97 98\r\n\
+    ///     a=rtpmap:96 VP8/90000\r\n\
+    ///     a=rtcp-fb:96 nack pli\r\n\
+    ///     a=rtpmap:97 rtx/90000\r\n\
+    ///     a=fmtp:97 apt=96\r\n\
+    ///     a=rtpmap:98 H264/90000\r\n"
+    /// ).unwrap();
+    ///
+    /// let media = &mut sdp.media[0];
+    /// let mapping = [(96, 100), (97, 101)].iter().copied().collect::<HashMap<u8, u8>>();
+    /// media.remap_payload_types(&mapping).unwrap();
+    ///
+    /// assert_eq!(media.media.fmts, vec![100, 101, 98]);
+    /// assert_eq!(media.attributes.rtpmap[&100].to_string(), "VP8/90000");
+    /// assert_eq!(media.attributes.fmtp[&101]["apt"], "100");
+    /// assert_eq!(media.attributes.rtcp_fb[0].pt, Some(100));
+    /// assert_eq!(media.attributes.rtpmap_order(), vec![100, 101, 98]);
+    ///
+    /// let lines = media.attributes.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec![
+    ///     "rtpmap:100 VP8/90000",
+    ///     "rtcp-fb:100 nack pli",
+    ///     "rtpmap:101 rtx/90000",
+    ///     "fmtp:101 apt=100",
+    ///     "rtpmap:98 H264/90000"
+    /// ]);
+    ///
+    /// let swap = [(100, 98), (98, 100)].iter().copied().collect::<HashMap<u8, u8>>();
+    /// media.remap_payload_types(&swap).unwrap();
+    /// assert_eq!(media.media.fmts, vec![98, 101, 100]);
+    /// assert_eq!(media.attributes.fmtp[&101]["apt"], "98");
+    ///
+    /// let collision = [(98, 101)].iter().copied().collect::<HashMap<u8, u8>>();
+    /// assert!(media.remap_payload_types(&collision).is_err());
+    /// assert_eq!(media.media.fmts, vec![98, 101, 100]);
+    /// ```
+    pub fn remap_payload_types(&mut self, mapping: &HashMap<u8, u8>) -> Result<()> {
+        let map = |pt: u8| mapping.get(&pt).copied().unwrap_or(pt);
+        let attributes = &mut self.attributes;
+        let mut pts = self.media.fmts.clone();
+        pts.extend(attributes.rtpmap.keys());
+        pts.extend(attributes.fmtp.keys());
+        pts.sort_unstable();
+        pts.dedup();
+
+        let mut targets = pts.iter().map(|pt| map(*pt)).collect::<Vec<u8>>();
+        ensure!(targets.iter().all(|pt| *pt < 128), "invalid payload type!");
+        targets.sort_unstable();
+        targets.dedup();
+        ensure!(targets.len() == pts.len(), "payload type collision!");
+
+        self.media.fmts.iter_mut().for_each(|pt| *pt = map(*pt));
+        attributes.rtpmap = attributes.rtpmap
+            .drain()
+            .map(|(pt, v)| (map(pt), v))
+            .collect();
+        attributes.fmtp = attributes.fmtp
+            .drain()
+            .map(|(pt, v)| (map(pt), v))
+            .collect();
+        attributes.framesize = attributes.framesize
+            .drain()
+            .map(|(pt, v)| (map(pt), v))
+            .collect();
+        for fb in attributes.rtcp_fb.iter_mut() {
+            fb.pt = fb.pt.map(map);
+        }
+
+        for params in attributes.fmtp.values_mut() {
+            let apt = params
+                .get("apt")
+                .and_then(|apt| apt.parse::<u8>().ok());
+            if let Some(apt) = apt {
+                params.insert("apt", Cow::Owned(map(apt).to_string()));
+            }
+        }
+
+        for line in attributes.lines.iter_mut() {
+            if let Some(remapped) = remap_line(line, map) {
+                *line = Cow::Owned(remapped);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// set the bandwidth cap.
+    ///
+    /// the cap is given in kilobits per second, and written both as 
+    /// "b=AS:" and as "b=TIAS:" in bits per second.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::media::*;
+    /// use std::convert::*;
+    ///
+    /// let media = Media::try_from("video 9 UDP/TLS/RTP/SAVPF 96").unwrap();
+    /// let mut media = MediaDescription::from(media);
+    ///
+    /// media.set_bandwidth(500);
+    /// assert_eq!(media.bandwidth.r#as, Some(500));
+    /// assert_eq!(media.bandwidth.tias, Some(500000));
+    /// ```
+    pub fn set_bandwidth(&mut self, kbps: usize) {
+        self.bandwidth.r#as = Some(kbps);
+        self.bandwidth.tias = Some(kbps * 1000);
+    }
+}
+
 impl fmt::Display for Media {
     /// # Unit Test
     ///
@@ -209,6 +550,8 @@ impl fmt::Display for Media {
 
         if !self.fmts.is_empty() {
             write!(f, " ")?;
+        } else if !self.protos.contains(&Proto::Rtp) {
+            write!(f, " *")?;
         }
 
         for (i, x) in self.fmts.iter().enumerate() {
@@ -248,24 +591,36 @@ impl<'a> TryFrom<&'a str> for Media {
     ///     media.fmts, 
     ///     vec![96, 97, 98, 99, 100, 101, 102, 121, 127, 120, 125]
     /// );
+    ///
+    /// let media: Media = Media::try_from("application 50000 TCP/BFCP *").unwrap();
+    /// assert_eq!(media.protos, vec![Proto::Tcp, Proto::Bfcp]);
+    /// assert!(media.fmts.is_empty());
+    /// assert_eq!(format!("{}", media), "application 50000 TCP/BFCP *");
+    ///
+    /// assert!(Media::try_from("audio 9 RTP/AVP *").is_err());
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let values = value.split(' ').collect::<Vec<&str>>();
-        ensure!(values.len() >= 3, "invalid media!");
+        let mut split = value.split(' ');
+        let encoding = Encoding::try_from(next_field(&mut split, "invalid media!")?)?;
+        let port = Port::try_from(next_field(&mut split, "invalid media!")?)?;
 
         let mut protos = Vec::with_capacity(5);
-        for p in values[2].split('/') {
+        for p in next_field(&mut split, "invalid media!")?.split('/') {
             protos.push(Proto::try_from(p)?);
         }
 
         let mut fmts = Vec::with_capacity(30);
-        for f in values[3..].iter() {
+        for f in split {
+            if f == "*" && !protos.contains(&Proto::Rtp) {
+                continue;
+            }
+
             fmts.push(f.parse()?);
         }
 
         Ok(Self {
-            encoding: Encoding::try_from(values[0])?,
-            port: Port::try_from(values[1])?,
+            encoding,
+            port,
             protos,
             fmts
         })
@@ -321,14 +676,14 @@ impl<'a> TryFrom<&'a str> for Port {
     /// assert_eq!(port.count, Some(2));
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let values = value.split('/').collect::<Vec<&str>>();
-        ensure!(!values.is_empty(), "invalid media port!");
+        let (num, count) = match value.split_once('/') {
+            Some((n, c)) => (n, Some(c)),
+            None => (value, None)
+        };
+
         Ok(Self {
-            num: values[0].parse()?,
-            count: match values.get(1) {
-                Some(c) => Some(c.parse()?),
-                None => None
-            }
+            num: num.parse()?,
+            count: count.map(str::parse).transpose()?
         })
     }
 }
@@ -403,6 +758,9 @@ impl fmt::Display for Proto {
             Self::Avp =>    "AVP",
             Self::Savp =>   "SAVP",
             Self::Savpf =>  "SAVPF",
+            Self::Tcp =>    "TCP",
+            Self::Avpf =>   "AVPF",
+            Self::Bfcp =>   "BFCP",
         })
     }
 }
@@ -420,6 +778,8 @@ impl<'a> TryFrom<&'a str> for Proto {
     /// assert_eq!(Proto::try_from("RTP").unwrap(), Proto::Rtp);
     /// assert_eq!(Proto::try_from("AVP").unwrap(), Proto::Avp);
     /// assert_eq!(Proto::try_from("SAVP").unwrap(), Proto::Savp);
+    /// assert_eq!(Proto::try_from("TCP").unwrap(), Proto::Tcp);
+    /// assert_eq!(Proto::try_from("BFCP").unwrap(), Proto::Bfcp);
     /// assert!(Proto::try_from("udp").is_err());
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
@@ -430,7 +790,38 @@ impl<'a> TryFrom<&'a str> for Proto {
             "AVP" =>    Ok(Self::Avp),
             "SAVP" =>   Ok(Self::Savp),
             "SAVPF" =>  Ok(Self::Savpf),
+            "TCP" =>    Ok(Self::Tcp),
+            "AVPF" =>   Ok(Self::Avpf),
+            "BFCP" =>   Ok(Self::Bfcp),
             _ => Err(anyhow!("invalid media proto!"))
         }
     }
 }
+
+/// renumber the payload type of a format attribute line, and the
+/// "apt" parameter of an "a=fmtp:" line, `None` when unchanged.
+fn remap_line(line: &str, map: impl Fn(u8) -> u8) -> Option<String> {
+    let attribute = Attribute::from(line);
+    let pt = attribute.format()?;
+    let (_, value) = attribute.value?.split_once(' ')?;
+    let value = match attribute.key() {
+        Some(Key::Fmtp) => value
+            .split(';')
+            .map(|param| match param.trim().split_once('=') {
+                Some(("apt", apt)) => match apt.parse::<u8>() {
+                    Ok(apt) => format!("apt={}", map(apt)),
+                    Err(_) => param.to_string()
+                },
+                _ => param.to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(";"),
+        _ => value.to_string()
+    };
+
+    let remapped = format!("{}:{} {}", attribute.name, map(pt), value);
+    match remapped != line {
+        true => Some(remapped),
+        false => None
+    }
+}
@@ -3,7 +3,10 @@ use anyhow::ensure;
 use super::{
     NetKind,
     AddrKind,
-    util::placeholder
+    util::{
+        placeholder,
+        next_field
+    }
 };
 
 use std::{
@@ -109,15 +112,17 @@ impl<'a> TryFrom<&'a str> for Origin<'a> {
     /// assert_eq!(instance.unicast_address, addr);
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let values = value.split(' ').collect::<Vec<&str>>();
-        ensure!(values.len() == 6, "invalid origin!");
-        Ok(Self {
-            sess_id: values[1],
-            sess_version: values[2].parse()?,
-            unicast_address: values[5].parse()?,
-            nettype: NetKind::try_from(values[3])?,
-            addrtype: AddrKind::try_from(values[4])?,
-            username: placeholder(values[0]),
-        })
+        let mut split = value.split(' ');
+        let origin = Self {
+            username: placeholder(next_field(&mut split, "invalid origin!")?),
+            sess_id: next_field(&mut split, "invalid origin!")?,
+            sess_version: next_field(&mut split, "invalid origin!")?.parse()?,
+            nettype: NetKind::try_from(next_field(&mut split, "invalid origin!")?)?,
+            addrtype: AddrKind::try_from(next_field(&mut split, "invalid origin!")?)?,
+            unicast_address: next_field(&mut split, "invalid origin!")?.parse()?,
+        };
+
+        ensure!(split.next().is_none(), "invalid origin!");
+        Ok(origin)
     }
 }
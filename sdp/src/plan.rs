@@ -0,0 +1,409 @@
+use super::attributes::{
+    Ssrc,
+    SsrcGroup,
+    Msid
+};
+
+use anyhow::Result;
+use std::convert::TryFrom;
+
+/// a media track of a Plan B media section.
+struct Track<'a> {
+    msid: Option<&'a str>,
+    ssrcs: Vec<u32>
+}
+
+/// session lines and media sections.
+///
+/// each media section starts with its "m=" line.
+struct Sections<'a> {
+    session: Vec<&'a str>,
+    media: Vec<Vec<&'a str>>,
+    eol: &'static str
+}
+
+impl<'a> Sections<'a> {
+    fn new(sdp: &'a str) -> Self {
+        let mut session = Vec::new();
+        let mut media: Vec<Vec<&'a str>> = Vec::new();
+        for line in sdp.lines().filter(|l| !l.is_empty()) {
+            if line.starts_with("m=") {
+                media.push(vec![line]);
+            } else if let Some(section) = media.last_mut() {
+                section.push(line);
+            } else {
+                session.push(line);
+            }
+        }
+
+        Self {
+            session,
+            media,
+            eol: if sdp.contains("\r\n") { "\r\n" } else { "\n" }
+        }
+    }
+}
+
+/// convert Plan B to Unified Plan.
+///
+/// Plan B signals all the tracks of a media type as SSRCs of a single
+/// "m=" line, Unified Plan uses one "m=" line per track.  Every media
+/// section carrying more than one track is split into one media section
+/// per track, the first keeps the original "a=mid:", the others get new
+/// numeric mids that are appended to the BUNDLE group.  Sources are
+/// assigned to tracks by their "msid" source-level attribute, or else by
+/// the "a=msid:" of the media section, and the members of a
+/// "a=ssrc-group:" follow the track of the group.  A media section with
+/// a single track, which is already Unified Plan, is left unchanged.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::plan::*;
+///
+/// let plan_b = "v=0\r\n\
+///     a=group:BUNDLE audio video\r\n\
+///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+///     a=mid:audio\r\n\
+///     a=ssrc:1 msid:stream a0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+///     a=mid:video\r\n\
+///     a=sendrecv\r\n\
+///     a=ssrc-group:FID 2 3\r\n\
+///     a=ssrc:2 msid:stream v0\r\n\
+///     a=ssrc:3 msid:stream v0\r\n\
+///     a=ssrc:4 msid:stream v1\r\n";
+///
+/// let unified = "v=0\r\n\
+///     a=group:BUNDLE audio video 2\r\n\
+///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+///     a=mid:audio\r\n\
+///     a=ssrc:1 msid:stream a0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+///     a=sendrecv\r\n\
+///     a=mid:video\r\n\
+///     a=msid:stream v0\r\n\
+///     a=ssrc-group:FID 2 3\r\n\
+///     a=ssrc:2 msid:stream v0\r\n\
+///     a=ssrc:3 msid:stream v0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+///     a=sendrecv\r\n\
+///     a=mid:2\r\n\
+///     a=msid:stream v1\r\n\
+///     a=ssrc:4 msid:stream v1\r\n";
+///
+/// assert_eq!(to_unified_plan(plan_b).unwrap(), unified);
+///
+/// let unified = "v=0\r\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+///     a=mid:0\r\n\
+///     a=msid:stream v0\r\n\
+///     a=ssrc:1 cname:x\r\n\
+///     a=ssrc:2 cname:x\r\n\
+///     a=ssrc-group:FID 1 2\r\n";
+///
+/// assert_eq!(to_unified_plan(unified).unwrap(), unified);
+/// ```
+pub fn to_unified_plan(sdp: &str) -> Result<String> {
+    let sections = Sections::new(sdp);
+    let mut next_mid = next_numeric_mid(&sections.media);
+    let mut bundle: Vec<(String, Vec<String>)> = Vec::new();
+    let mut media: Vec<Vec<String>> = Vec::new();
+
+    for section in &sections.media {
+        let tracks = tracks(section)?;
+        if tracks.len() < 2 {
+            media.push(section.iter().map(|l| l.to_string()).collect());
+            continue;
+        }
+
+        let mid = attribute(section, "mid");
+        let mut added = Vec::new();
+        let groups = lines_of(section, "ssrc-group")
+            .map(SsrcGroup::try_from)
+            .collect::<Result<Vec<SsrcGroup>>>()?;
+
+        for (index, track) in tracks.iter().enumerate() {
+            let mut lines = section
+                .iter()
+                .filter(|l| !is_source_line(l))
+                .filter(|l| !l.starts_with("a=mid:") && !l.starts_with("a=msid:"))
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>();
+            let track_mid = match (index, mid) {
+                (0, Some(m)) => Some(m.to_string()),
+                (0, None) => None,
+                _ => {
+                    next_mid += 1;
+                    added.push((next_mid - 1).to_string());
+                    Some((next_mid - 1).to_string())
+                }
+            };
+
+            if let Some(m) = track_mid {
+                lines.push(format!("a=mid:{}", m));
+            }
+
+            if let Some(msid) = track.msid {
+                lines.push(format!("a=msid:{}", msid));
+            }
+
+            for group in &groups {
+                if group.ssrcs.iter().all(|s| track.ssrcs.contains(s)) {
+                    lines.push(format!("a=ssrc-group:{}", group));
+                }
+            }
+
+            for line in lines_of(section, "ssrc") {
+                if track.ssrcs.contains(&Ssrc::try_from(line)?.id) {
+                    lines.push(format!("a=ssrc:{}", line));
+                }
+            }
+
+            media.push(lines);
+        }
+
+        if let Some(m) = mid {
+            bundle.push((m.to_string(), added));
+        }
+    }
+
+    let session = sections.session.iter().map(|line| {
+        match line.strip_prefix("a=group:BUNDLE") {
+            None => line.to_string(),
+            Some(mids) => {
+                let mut group = "a=group:BUNDLE".to_string();
+                for mid in mids.split(' ').filter(|m| !m.is_empty()) {
+                    group.push(' ');
+                    group.push_str(mid);
+                    if let Some((_, added)) = bundle.iter().find(|(m, _)| m == mid) {
+                        for a in added {
+                            group.push(' ');
+                            group.push_str(a);
+                        }
+                    }
+                }
+
+                group
+            }
+        }
+    }).collect::<Vec<String>>();
+
+    Ok(join(session, media, sections.eol))
+}
+
+/// convert Unified Plan to Plan B.
+///
+/// All the audio media sections are merged into the first audio media
+/// section, and all the video media sections into the first video media
+/// section, by moving their source-level attributes.  When a source has
+/// no "msid" source-level attribute, one is added from the "a=msid:" of
+/// its media section, since Plan B has no other way to identify the track.
+/// The mids of the merged media sections are removed from the BUNDLE
+/// group, other media types are left untouched.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::plan::*;
+///
+/// let unified = "v=0\n\
+///     a=group:BUNDLE 0 1 2\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96\n\
+///     a=mid:0\n\
+///     a=msid:stream v0\n\
+///     a=ssrc:2 cname:x\n\
+///     m=audio 9 UDP/TLS/RTP/SAVPF 111\n\
+///     a=mid:1\n\
+///     a=msid:stream a0\n\
+///     a=ssrc:1 msid:stream a0\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96\n\
+///     a=mid:2\n\
+///     a=msid:stream v1\n\
+///     a=ssrc:4 cname:x\n";
+///
+/// let plan_b = "v=0\n\
+///     a=group:BUNDLE 0 1\n\
+///     m=video 9 UDP/TLS/RTP/SAVPF 96\n\
+///     a=mid:0\n\
+///     a=ssrc:2 cname:x\n\
+///     a=ssrc:2 msid:stream v0\n\
+///     a=ssrc:4 cname:x\n\
+///     a=ssrc:4 msid:stream v1\n\
+///     m=audio 9 UDP/TLS/RTP/SAVPF 111\n\
+///     a=mid:1\n\
+///     a=ssrc:1 msid:stream a0\n";
+///
+/// assert_eq!(to_plan_b(unified).unwrap(), plan_b);
+/// ```
+pub fn to_plan_b(sdp: &str) -> Result<String> {
+    let sections = Sections::new(sdp);
+    let mut media: Vec<(&str, Vec<String>)> = Vec::new();
+    let mut removed = Vec::new();
+
+    for section in &sections.media {
+        let kind = section[0][2..].split(' ').next().unwrap_or("");
+        let mut sources = Vec::new();
+        for group in lines_of(section, "ssrc-group") {
+            sources.push(format!("a=ssrc-group:{}", group));
+        }
+
+        let msid = attribute(section, "msid")
+            .map(Msid::try_from)
+            .transpose()?;
+        let ssrcs = lines_of(section, "ssrc")
+            .map(Ssrc::try_from)
+            .collect::<Result<Vec<Ssrc>>>()?;
+        for (index, ssrc) in ssrcs.iter().enumerate() {
+            sources.push(format!("a=ssrc:{}", ssrc));
+            let is_last = ssrcs[index + 1..].iter().all(|s| s.id != ssrc.id);
+            let has_msid = ssrcs.iter().any(|s| s.id == ssrc.id && s.attribute == "msid");
+            if let (true, false, Some(m)) = (is_last, has_msid, &msid) {
+                sources.push(format!("a=ssrc:{} msid:{}", ssrc.id, m));
+            }
+        }
+
+        let host = match kind {
+            "audio" | "video" => media.iter_mut().find(|(k, _)| *k == kind),
+            _ => None
+        };
+
+        match host {
+            Some((_, lines)) => {
+                lines.append(&mut sources);
+                if let Some(mid) = attribute(section, "mid") {
+                    removed.push(mid);
+                }
+            },
+            None => {
+                let mut lines = section
+                    .iter()
+                    .filter(|l| !is_source_line(l) && !l.starts_with("a=msid:"))
+                    .map(|l| l.to_string())
+                    .collect::<Vec<String>>();
+                lines.append(&mut sources);
+                media.push((kind, lines));
+            }
+        }
+    }
+
+    let session = sections.session.iter().map(|line| {
+        match line.strip_prefix("a=group:BUNDLE") {
+            None => line.to_string(),
+            Some(mids) => {
+                let mut group = "a=group:BUNDLE".to_string();
+                for mid in mids.split(' ').filter(|m| !m.is_empty() && !removed.contains(m)) {
+                    group.push(' ');
+                    group.push_str(mid);
+                }
+
+                group
+            }
+        }
+    }).collect::<Vec<String>>();
+
+    let media = media.into_iter().map(|(_, lines)| lines).collect();
+    Ok(join(session, media, sections.eol))
+}
+
+/// group the sources of a media section into tracks.
+///
+/// the members of an "a=ssrc-group:" belong to the same track, the
+/// track is identified by the "msid" source-level attribute of one of
+/// its sources, or else by the "a=msid:" of the media section.
+fn tracks<'a>(section: &[&'a str]) -> Result<Vec<Track<'a>>> {
+    let ssrcs = lines_of(section, "ssrc")
+        .map(Ssrc::try_from)
+        .collect::<Result<Vec<Ssrc>>>()?;
+    let groups = lines_of(section, "ssrc-group")
+        .map(SsrcGroup::try_from)
+        .collect::<Result<Vec<SsrcGroup>>>()?;
+    let media_msid = attribute(section, "msid");
+
+    let mut tracks: Vec<Track> = Vec::new();
+    for ssrc in &ssrcs {
+        if tracks.iter().any(|t| t.ssrcs.contains(&ssrc.id)) {
+            continue;
+        }
+
+        let mut members = vec![ssrc.id];
+        let mut index = 0;
+        while index < members.len() {
+            let id = members[index];
+            for group in groups.iter().filter(|g| g.ssrcs.contains(&id)) {
+                for s in &group.ssrcs {
+                    if !members.contains(s) {
+                        members.push(*s);
+                    }
+                }
+            }
+
+            index += 1;
+        }
+
+        let msid = members
+            .iter()
+            .find_map(|id| {
+                ssrcs.iter()
+                    .find(|s| s.id == *id && s.attribute == "msid")
+                    .and_then(|s| s.value)
+            })
+            .or(media_msid);
+        match tracks.iter_mut().find(|t| msid.is_some() && t.msid == msid) {
+            Some(track) => for id in members {
+                if !track.ssrcs.contains(&id) {
+                    track.ssrcs.push(id);
+                }
+            },
+            None => tracks.push(Track {
+                msid,
+                ssrcs: members
+            })
+        }
+    }
+
+    Ok(tracks)
+}
+
+/// the values of an attribute in a media section.
+fn lines_of<'a, 'b>(
+    section: &'b [&'a str],
+    name: &'b str
+) -> impl Iterator<Item = &'a str> + 'b {
+    section.iter().filter_map(move |line| {
+        line.strip_prefix("a=")
+            .and_then(|l| l.strip_prefix(name))
+            .and_then(|l| l.strip_prefix(':'))
+    })
+}
+
+/// the first value of an attribute in a media section.
+fn attribute<'a>(section: &[&'a str], name: &str) -> Option<&'a str> {
+    lines_of(section, name).next()
+}
+
+fn is_source_line(line: &str) -> bool {
+    line.starts_with("a=ssrc:") || line.starts_with("a=ssrc-group:")
+}
+
+/// the first numeric mid not used by any media section.
+fn next_numeric_mid(media: &[Vec<&str>]) -> u32 {
+    media
+        .iter()
+        .filter_map(|section| attribute(section, "mid"))
+        .filter_map(|mid| mid.parse::<u32>().ok())
+        .map(|mid| mid + 1)
+        .max()
+        .unwrap_or(0)
+        .max(media.len() as u32)
+}
+
+fn join(session: Vec<String>, media: Vec<Vec<String>>, eol: &str) -> String {
+    let mut sdp = String::new();
+    for line in session.iter().chain(media.iter().flatten()) {
+        sdp.push_str(line);
+        sdp.push_str(eol);
+    }
+
+    sdp
+}
@@ -0,0 +1,174 @@
+/// the mask of a secret value.
+const MASK: &str = "***";
+
+/// mask an address, keeping its family so the description still parses.
+fn address(value: &str) -> &'static str {
+    if value.contains(':') {
+        "::"
+    } else {
+        "0.0.0.0"
+    }
+}
+
+/// replace the fields at the given indexes of a space separated value.
+fn fields<F>(value: &str, mask: F) -> String
+where
+    F: Fn(usize, &str) -> Option<&'static str>
+{
+    value
+        .split(' ')
+        .enumerate()
+        .map(|(i, field)| mask(i, field).unwrap_or(field))
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// mask a connection data value.
+///
+/// c=<nettype> <addrtype> <connection-address>
+fn connection(value: &str) -> String {
+    fields(value, |i, field| match i {
+        2 => Some(address(field.split('/').next().unwrap_or(field))),
+        _ => None
+    })
+}
+
+/// mask a candidate value.
+///
+/// a=candidate:<foundation> <component-id> <transport> <priority>
+///     <connection-address> <port> typ <cand-type> [raddr <addr>] ...
+fn candidate(value: &str) -> String {
+    let raddr = value
+        .split(' ')
+        .position(|f| f == "raddr")
+        .map(|i| i + 1);
+    fields(value, |i, field| {
+        if i == 4 || Some(i) == raddr {
+            Some(address(field))
+        } else {
+            None
+        }
+    })
+}
+
+/// mask a remote candidates value.
+///
+/// a=remote-candidates:<component-id> <connection-address> <port> ...
+fn remote_candidates(value: &str) -> String {
+    fields(value, |i, field| match i % 3 {
+        1 => Some(address(field)),
+        _ => None
+    })
+}
+
+/// mask one line.
+fn line(line: &str) -> String {
+    let (key, value) = match line.split_once('=') {
+        Some(kv) => kv,
+        None => return line.to_string()
+    };
+
+    let value = match key {
+        "o" => fields(value, |i, field| match i {
+            5 => Some(address(field)),
+            _ => None
+        }),
+        "c" => connection(value),
+        "e" | "p" => MASK.to_string(),
+        "k" => match value.split_once(':') {
+            Some((method, _)) => format!("{}:{}", method, MASK),
+            None => value.to_string()
+        },
+        "a" => match value.split_once(':') {
+            None => value.to_string(),
+            Some((name, attribute)) => format!("{}:{}", name, match name {
+                "ice-ufrag" | "ice-pwd" => MASK.to_string(),
+                "candidate" => candidate(attribute),
+                "remote-candidates" => remote_candidates(attribute),
+                "rtcp" => match attribute.split_once(' ') {
+                    Some((port, data)) => format!("{} {}", port, connection(data)),
+                    None => attribute.to_string()
+                },
+                "fingerprint" => fields(attribute, |i, _| match i {
+                    0 => None,
+                    _ => Some(MASK)
+                }),
+                "crypto" => fields(attribute, |i, field| match i {
+                    2 => Some(match field.split_once(':') {
+                        Some(("inline", _)) => "inline:***",
+                        _ => MASK
+                    }),
+                    _ => None
+                }),
+                _ => attribute.to_string()
+            })
+        },
+        _ => value.to_string()
+    };
+
+    format!("{}={}", key, value)
+}
+
+/// redact a session description for logging.
+///
+/// Produces a copy of the description with the addresses of the
+/// "o=", "c=", "a=rtcp:", "a=candidate:" and "a=remote-candidates:"
+/// lines, the "e=" and "p=" contacts, the ICE credentials,
+/// the certificate fingerprints and the "a=crypto:" and "k=" keys
+/// masked.  Addresses are replaced by the unspecified address of their
+/// family so the copy remains a valid description, the line endings are
+/// kept.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::redact::*;
+/// use std::convert::TryFrom;
+///
+/// let sdp = "v=0\r\n\
+///     o=- 4611731400430051336 2 IN IP4 192.168.1.10\r\n\
+///     s=-\r\n\
+///     e=j.doe@example.com (Jane Doe)\r\n\
+///     p=+1 617 555-6011\r\n\
+///     c=IN IP4 203.0.113.1/127\r\n\
+///     k=base64:c2VjcmV0\r\n\
+///     a=ice-ufrag:F7gI\r\n\
+///     a=ice-pwd:x9cml/YzichV2+XlhiMu8g\r\n\
+///     a=fingerprint:sha-256 49:66:12:17:0D:1C:91:AE\r\n\
+///     m=audio 9 RTP/SAVPF 111\r\n\
+///     a=rtcp:9 IN IP6 2001:db8::1\r\n\
+///     a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:32\r\n\
+///     a=candidate:1 1 udp 2113937151 192.168.1.10 54400 typ srflx raddr 10.0.0.1 rport 54400\r\n\
+///     a=remote-candidates:1 192.168.1.20 54400 2 2001:db8::2 54401\r\n\
+///     a=mid:0\r\n";
+///
+/// let redacted = "v=0\r\n\
+///     o=- 4611731400430051336 2 IN IP4 0.0.0.0\r\n\
+///     s=-\r\n\
+///     e=***\r\n\
+///     p=***\r\n\
+///     c=IN IP4 0.0.0.0\r\n\
+///     k=base64:***\r\n\
+///     a=ice-ufrag:***\r\n\
+///     a=ice-pwd:***\r\n\
+///     a=fingerprint:sha-256 ***\r\n\
+///     m=audio 9 RTP/SAVPF 111\r\n\
+///     a=rtcp:9 IN IP6 ::\r\n\
+///     a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:***\r\n\
+///     a=candidate:1 1 udp 2113937151 0.0.0.0 54400 typ srflx raddr 0.0.0.0 rport 54400\r\n\
+///     a=remote-candidates:1 0.0.0.0 54400 2 :: 54401\r\n\
+///     a=mid:0\r\n";
+///
+/// assert_eq!(redact(sdp), redacted);
+/// assert!(sdp::Sdp::try_from(redact(sdp).as_str()).is_ok());
+/// ```
+pub fn redact(sdp: &str) -> String {
+    let eol = if sdp.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut output = String::with_capacity(sdp.len());
+    for value in sdp.lines().filter(|l| !l.is_empty()) {
+        output.push_str(&line(value));
+        output.push_str(eol);
+    }
+
+    output
+}
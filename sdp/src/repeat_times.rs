@@ -1,4 +1,7 @@
-use super::util::short_time;
+use super::util::{
+    short_time,
+    next_field
+};
 use anyhow::{
     ensure,
     Result
@@ -49,7 +52,9 @@ use std::{
 pub struct RepeatTimes {
     pub repeat_interval: f64,
     pub active_duration: f64,
-    pub offsets_from_start_time: f64
+    /// one or more offsets from the start time of the "t=" line,
+    /// every offset starts a new active duration in each interval.
+    pub offsets_from_start_time: Vec<f64>
 }
 
 impl fmt::Display for RepeatTimes {
@@ -62,7 +67,7 @@ impl fmt::Display for RepeatTimes {
     /// let timing = RepeatTimes {
     ///     repeat_interval: 86400.0,
     ///     active_duration: 3600.0,
-    ///     offsets_from_start_time: 1.0
+    ///     offsets_from_start_time: vec![0.0, 1.0]
     /// };
     ///
     /// assert_eq!(format!("{}", timing), temp);
@@ -70,11 +75,16 @@ impl fmt::Display for RepeatTimes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f, 
-            "{} {} 0 {}",
+            "{} {}",
             self.repeat_interval,
-            self.active_duration,
-            self.offsets_from_start_time
-        )
+            self.active_duration
+        )?;
+
+        for offset in &self.offsets_from_start_time {
+            write!(f, " {}", offset)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -91,22 +101,38 @@ impl<'a> TryFrom<&'a str> for RepeatTimes {
     /// 
     /// assert_eq!(instance.repeat_interval, 86400.0);
     /// assert_eq!(instance.active_duration, 3600.0);
-    /// assert_eq!(instance.offsets_from_start_time, 1.0);
+    /// assert_eq!(instance.offsets_from_start_time, vec![0.0, 1.0]);
     ///
     /// let temp = "86400 3600 0 1";
     /// let instance: RepeatTimes = RepeatTimes::try_from(temp).unwrap();
     /// 
     /// assert_eq!(instance.repeat_interval, 86400.0);
     /// assert_eq!(instance.active_duration, 3600.0);
-    /// assert_eq!(instance.offsets_from_start_time, 1.0);
+    /// assert_eq!(instance.offsets_from_start_time, vec![0.0, 1.0]);
+    ///
+    /// let temp = "7d 1h 0 25h";
+    /// let instance: RepeatTimes = RepeatTimes::try_from(temp).unwrap();
+    /// 
+    /// assert_eq!(instance.repeat_interval, 604800.0);
+    /// assert_eq!(instance.active_duration, 3600.0);
+    /// assert_eq!(instance.offsets_from_start_time, vec![0.0, 90000.0]);
+    /// assert_eq!(format!("{}", instance), "604800 3600 0 90000");
+    ///
+    /// assert!(RepeatTimes::try_from("7d 1h").is_err());
+    /// assert!(RepeatTimes::try_from("7d 1h 0 1.5x").is_err());
+    /// assert!(RepeatTimes::try_from("7d 1h 0 1é").is_err());
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let values = value.split(' ').collect::<Vec<&str>>();
-        ensure!(values.len() == 4, "invalid timing!");
-        Ok(Self {
-            repeat_interval: short_time(values[0])?,
-            active_duration: short_time(values[1])?,
-            offsets_from_start_time: short_time(values[3])?
-        })
+        let mut split = value.split(' ');
+        let repeat_times = Self {
+            repeat_interval: short_time(next_field(&mut split, "invalid timing!")?)?,
+            active_duration: short_time(next_field(&mut split, "invalid timing!")?)?,
+            offsets_from_start_time: split
+                .map(short_time)
+                .collect::<Result<Vec<f64>>>()?
+        };
+
+        ensure!(!repeat_times.offsets_from_start_time.is_empty(), "invalid timing!");
+        Ok(repeat_times)
     }
 }
@@ -1,5 +1,8 @@
-use super::util::short_time;
-use itertools::Itertools;
+use super::util::{
+    short_time,
+    next_field
+};
+
 use std::{
     convert::TryFrom,
     fmt
@@ -149,11 +152,20 @@ impl<'a> TryFrom<&'a str> for TimeZones {
     /// assert_eq!(instance.get_values()[0].offset, 100.0);
     /// assert_eq!(instance.get_values()[1].adjustment_time, 2898848070);
     /// assert_eq!(instance.get_values()[1].offset, 0.0);
+    ///
+    /// let temp = "2882844526 -1h 2898848070 0";
+    /// let instance: TimeZones = TimeZones::try_from(temp).unwrap();
+    ///
+    /// assert_eq!(instance.get_values()[0].offset, -3600.0);
+    /// assert!(TimeZones::try_from("2882844526 -1h 2898848070").is_err());
+    /// assert!(TimeZones::try_from("2882844526 1é").is_err());
     /// ```
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         let mut values = Vec::with_capacity(5);
-        for (a, b) in value.split(' ').tuples() {
-            values.push(TimeZone::try_from((a, b))?);
+        let mut split = value.split(' ');
+        while let Some(adjustment_time) = split.next() {
+            let offset = next_field(&mut split, "invalid time zones!")?;
+            values.push(TimeZone::try_from((adjustment_time, offset))?);
         }
 
         Ok(Self(values))
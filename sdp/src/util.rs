@@ -1,5 +1,6 @@
 use anyhow::{
     Result,
+    ensure,
     anyhow
 };
 
@@ -15,16 +16,21 @@ use anyhow::{
 /// assert_eq!(short_time("1m").unwrap(), 60.0);
 /// assert_eq!(short_time("1s").unwrap(), 1.0);
 /// assert_eq!(short_time("100").unwrap(), 100.0);
+/// assert_eq!(short_time("-1h").unwrap(), -3600.0);
+/// assert!(short_time("").is_err());
+/// assert!(short_time("1w").is_err());
+/// assert!(short_time("1é").is_err());
+/// assert!(short_time("é").is_err());
 /// ```
 pub fn short_time(time: &str) -> Result<f64> {
-    let (value, last) = time.split_at(time.len() - 1);
-    Ok(match last {
-        "d" => parse_f64(value)? * 86400.0,
-        "h" => parse_f64(value)? * 3600.0,
-        "m" => parse_f64(value)? * 60.0,
-        "s" => parse_f64(value)?,
-        _ => time.parse::<f64>()?
-    })
+    ensure!(!time.is_empty(), "invalid typed time!");
+    for (unit, seconds) in [("d", 86400.0), ("h", 3600.0), ("m", 60.0), ("s", 1.0)] {
+        if let Some(value) = time.strip_suffix(unit) {
+            return Ok(parse_f64(value)? * seconds)
+        }
+    }
+
+    Ok(time.parse::<f64>()?)
 }
 
 /// placeholder char.
@@ -86,6 +92,28 @@ pub fn tuple3_from_split<'a>(
     Ok((v1, v2, v3))
 }
 
+/// take the next field of a split.
+///
+/// # Unit Test
+///
+/// ```
+/// use sdp::util::*;
+///
+/// let mut split = "1 2".split(' ');
+/// assert_eq!(next_field(&mut split, "").unwrap(), "1");
+/// assert_eq!(next_field(&mut split, "").unwrap(), "2");
+/// assert!(next_field(&mut split, "").is_err());
+/// ```
+pub fn next_field<'a, T>(
+    split: &mut T,
+    msg: &'static str
+) -> Result<&'a str>
+where
+    T: Iterator<Item = &'a str>
+{
+    split.next().ok_or_else(|| anyhow!(msg))
+}
+
 fn parse_f64(value: &str) -> Result<f64> {
     Ok(value.parse::<f64>()?)
 }
\ No newline at end of file
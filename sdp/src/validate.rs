@@ -0,0 +1,194 @@
+use super::attributes::{
+    static_payload_type,
+    Attributes
+};
+use super::media::Proto;
+use super::Sdp;
+use std::fmt;
+
+/// semantic violation.
+///
+/// the `media` field is the index of the offending media section.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// the mandatory "o=" line is missing.
+    MissingOrigin,
+    /// the mandatory "t=" line is missing.
+    MissingTiming,
+    /// a dynamic payload type of the "m=" line has no "a=rtpmap:".
+    MissingRtpMap { media: usize, pt: u8 },
+    /// an "a=fmtp:" refers to a payload type not in the "m=" line.
+    UndeclaredFmtp { media: usize, pt: u8 },
+    /// more than one direction attribute in the same scope,
+    /// `None` is the session level.
+    ConflictingDirection { media: Option<usize> },
+    /// the "a=rtpmap:" clock rate is not the one of the codec.
+    ImplausibleClockRate { media: usize, pt: u8, rate: u64 },
+}
+
+impl<'a> Sdp<'a> {
+    /// semantic validation.
+    ///
+    /// checks the consistency between fields that the parser accepts
+    /// one line at a time, and returns every violation found instead
+    /// of stopping at the first one.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::validate::Violation;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let sdp = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     o=- 0 0 IN IP4 127.0.0.1\r\n\
+    ///     s=-\r\n\
+    ///     t=0 0\r\n\
+    ///     m=video 9 UDP/TLS/RTP/SAVPF 0 96\r\n\
+    ///     a=rtpmap:96 VP8/90000\r\n\
+    ///     a=fmtp:96 max-fr=30\r\n"
+    /// ).unwrap();
+    ///
+    /// assert!(sdp.validate().is_empty());
+    ///
+    /// let sdp = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     s=-\r\n\
+    ///     a=sendonly\r\n\
+    ///     a=recvonly\r\n\
+    ///     m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+    ///     a=rtpmap:96 VP8/90000\r\n\
+    ///     a=fmtp:98 max-fr=30\r\n"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(sdp.validate(), vec![
+    ///     Violation::MissingOrigin,
+    ///     Violation::MissingTiming,
+    ///     Violation::ConflictingDirection { media: None },
+    ///     Violation::MissingRtpMap { media: 0, pt: 97 },
+    ///     Violation::UndeclaredFmtp { media: 0, pt: 98 },
+    /// ]);
+    /// ```
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if self.origin.is_none() {
+            violations.push(Violation::MissingOrigin);
+        }
+
+        if self.timing.is_none() {
+            violations.push(Violation::MissingTiming);
+        }
+
+        if has_conflicting_direction(&self.attributes) {
+            violations.push(Violation::ConflictingDirection { media: None });
+        }
+
+        for (index, media) in self.media.iter().enumerate() {
+            let attributes = &media.attributes;
+            if media.media.protos.contains(&Proto::Rtp) {
+                for pt in &media.media.fmts {
+                    if !attributes.rtpmap.contains_key(pt) && static_payload_type(*pt).is_none() {
+                        violations.push(Violation::MissingRtpMap { media: index, pt: *pt });
+                    }
+                }
+            }
+
+            let mut fmtps = attributes.fmtp.keys().copied().collect::<Vec<u8>>();
+            fmtps.sort_unstable();
+            for pt in fmtps {
+                if !media.media.fmts.contains(&pt) {
+                    violations.push(Violation::UndeclaredFmtp { media: index, pt });
+                }
+            }
+
+            if has_conflicting_direction(attributes) {
+                violations.push(Violation::ConflictingDirection { media: Some(index) });
+            }
+        }
+
+        violations
+    }
+}
+
+impl<'a> Sdp<'a> {
+    /// clock rate validation.
+    ///
+    /// an optional step next to `validate`, flags the "a=rtpmap:" of
+    /// the known codecs whose clock rate is not the one of the payload
+    /// format, such as VP8 not at 90000 or opus not at 48000, which
+    /// would otherwise play at the wrong pitch or speed.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::validate::Violation;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let sdp = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111 0 101\r\n\
+    ///     a=rtpmap:111 opus/44100/2\r\n\
+    ///     a=rtpmap:0 PCMU/8000\r\n\
+    ///     a=rtpmap:101 telephone-event/48000\r\n\
+    ///     m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+    ///     a=rtpmap:96 VP8/9000\r\n"
+    /// ).unwrap();
+    ///
+    /// assert_eq!(sdp.validate_clock_rates(), vec![
+    ///     Violation::ImplausibleClockRate { media: 0, pt: 111, rate: 44100 },
+    ///     Violation::ImplausibleClockRate { media: 1, pt: 96, rate: 9000 },
+    /// ]);
+    /// ```
+    pub fn validate_clock_rates(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (index, media) in self.media.iter().enumerate() {
+            let mut pts = media.attributes.rtpmap.keys().copied().collect::<Vec<u8>>();
+            pts.sort_unstable();
+            for pt in pts {
+                let rtp = &media.attributes.rtpmap[&pt];
+                if let (Some(rates), Some(rate)) = (rtp.codec.clock_rates(), rtp.frequency) {
+                    if !rates.contains(&rate) {
+                        violations.push(Violation::ImplausibleClockRate { media: index, pt, rate });
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+fn has_conflicting_direction(attributes: &Attributes) -> bool {
+    [
+        attributes.sendrecv,
+        attributes.sendonly,
+        attributes.recvonly,
+        attributes.inactive
+    ].iter().filter(|x| **x).count() > 1
+}
+
+impl fmt::Display for Violation {
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::validate::Violation;
+    ///
+    /// assert_eq!(
+    ///     format!("{}", Violation::MissingRtpMap { media: 1, pt: 96 }),
+    ///     "media 1: payload type 96 has no rtpmap!"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingOrigin => write!(f, "missing origin!"),
+            Self::MissingTiming => write!(f, "missing timing!"),
+            Self::MissingRtpMap { media, pt } => write!(f, "media {}: payload type {} has no rtpmap!", media, pt),
+            Self::UndeclaredFmtp { media, pt } => write!(f, "media {}: fmtp for undeclared payload type {}!", media, pt),
+            Self::ConflictingDirection { media: None } => write!(f, "conflicting direction!"),
+            Self::ConflictingDirection { media: Some(media) } => write!(f, "media {}: conflicting direction!", media),
+            Self::ImplausibleClockRate { media, pt, rate } => write!(f, "media {}: payload type {} has implausible clock rate {}!", media, pt, rate),
+        }
+    }
+}
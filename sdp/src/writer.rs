@@ -0,0 +1,283 @@
+use super::attributes::{
+    Attributes,
+    Key
+};
+
+use super::bandwidth::{
+    Bandwidths,
+    BwKind
+};
+
+use super::media::MediaDescription;
+use super::Sdp;
+use std::{
+    fmt,
+    io
+};
+
+/// line ending.
+///
+/// RFC 8866 requires CRLF, some parsers accept a bare LF, but
+/// strict SIP stacks reject it, so LF is only meant for debugging.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum LineEnding {
+    #[default]
+    Crlf,
+    Lf
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Crlf => "\r\n",
+            Self::Lf => "\n"
+        }
+    }
+}
+
+/// line writer.
+///
+/// writes the "<type>=<value>" lines of a session description, each
+/// followed by the line ending.
+pub struct Writer<'w, W> {
+    sink: &'w mut W,
+    ending: LineEnding
+}
+
+impl<'w, W: fmt::Write> Writer<'w, W> {
+    pub fn new(sink: &'w mut W, ending: LineEnding) -> Self {
+        Self {
+            sink,
+            ending
+        }
+    }
+
+    /// write a line.
+    pub fn line(&mut self, key: &str, value: impl fmt::Display) -> fmt::Result {
+        write!(self.sink, "{}{}{}", key, value, self.ending.as_str())
+    }
+
+    /// write an attribute line, "a=<name>:<value>".
+    pub fn attribute(&mut self, key: Key, value: impl fmt::Display) -> fmt::Result {
+        write!(self.sink, "a={}:{}{}", key, value, self.ending.as_str())
+    }
+
+    /// write a flag attribute line, "a=<name>".
+    pub fn flag(&mut self, key: Key) -> fmt::Result {
+        write!(self.sink, "a={}{}", key, self.ending.as_str())
+    }
+
+    /// write the "b=" lines.
+    pub fn bandwidths(&mut self, bandwidths: &Bandwidths) -> fmt::Result {
+        for (kind, value) in [
+            (BwKind::CT, bandwidths.ct),
+            (BwKind::AS, bandwidths.r#as),
+            (BwKind::TIAS, bandwidths.tias)
+        ].iter() {
+            if let Some(bandwidth) = value {
+                self.line("b=", format_args!("{}:{}", kind, bandwidth))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// write the "a=" lines.
+    ///
+    /// the attributes are written verbatim in the order of appearance,
+    /// see `Attributes::lines`, so that the order of preference of the
+    /// formats and the vendor-specific attributes survive.
+    pub fn attributes(&mut self, attributes: &Attributes) -> fmt::Result {
+        for line in &attributes.lines {
+            self.line("a=", line)?;
+        }
+
+        Ok(())
+    }
+
+    /// write a media description.
+    pub fn media(&mut self, media: &MediaDescription) -> fmt::Result {
+        self.line("m=", &media.media)?;
+        if let Some(info) = media.info {
+            self.line("i=", info)?;
+        }
+
+        if let Some(connection) = &media.connection {
+            self.line("c=", connection)?;
+        }
+
+        self.bandwidths(&media.bandwidth)?;
+        if let Some(key) = &media.encryption_key {
+            self.line("k=", key)?;
+        }
+
+        self.attributes(&media.attributes)
+    }
+
+    /// write a session description.
+    pub fn sdp(&mut self, sdp: &Sdp) -> fmt::Result {
+        self.line("v=", 0)?;
+        if let Some(origin) = &sdp.origin {
+            self.line("o=", origin)?;
+        }
+
+        self.line("s=", sdp.session_name.unwrap_or("-"))?;
+        for (key, value) in [
+            ("i=", sdp.session_info),
+            ("u=", sdp.uri),
+            ("e=", sdp.email),
+            ("p=", sdp.phone)
+        ] {
+            if let Some(value) = value {
+                self.line(key, value)?;
+            }
+        }
+
+        if let Some(connection) = &sdp.connection {
+            self.line("c=", connection)?;
+        }
+
+        self.bandwidths(&sdp.bandwidth)?;
+        if let Some(timing) = &sdp.timing {
+            self.line("t=", timing)?;
+        }
+
+        for repeat_times in &sdp.repeat_times {
+            self.line("r=", repeat_times)?;
+        }
+
+        if let Some(time_zones) = &sdp.time_zones {
+            self.line("z=", time_zones)?;
+        }
+
+        if let Some(key) = &sdp.encryption_key {
+            self.line("k=", key)?;
+        }
+
+        self.attributes(&sdp.attributes)?;
+        for media in &sdp.media {
+            self.media(media)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// adapts an io sink, keeping the io error which `fmt::Error`
+/// cannot carry.
+struct IoSink<'w, W> {
+    inner: &'w mut W,
+    error: Option<io::Error>
+}
+
+impl<'w, W: io::Write> fmt::Write for IoSink<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+impl<'a> Sdp<'a> {
+    /// encode to a text sink.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::writer::LineEnding;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let sdp = Sdp::try_from(
+    ///     "v=0\r\n\
+    ///     o=- 0 0 IN IP4 127.0.0.1\r\n\
+    ///     s=-\r\n\
+    ///     i=A Seminar on the session description protocol\r\n\
+    ///     t=0 0\r\n\
+    ///     a=group:BUNDLE 0\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+    ///     i=main audio\r\n\
+    ///     c=IN IP4 0.0.0.0\r\n\
+    ///     b=AS:64\r\n\
+    ///     a=mid:0\r\n\
+    ///     a=sendrecv\r\n\
+    ///     a=rtpmap:111 opus/48000/2\r\n\
+    ///     a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+    ///     a=ptime:20\r\n"
+    /// ).unwrap();
+    ///
+    /// let mut text = String::new();
+    /// sdp.encode(&mut text, LineEnding::Lf).unwrap();
+    /// assert_eq!(text, "v=0\n\
+    ///     o=- 0 0 IN IP4 127.0.0.1\n\
+    ///     s=-\n\
+    ///     i=A Seminar on the session description protocol\n\
+    ///     t=0 0\n\
+    ///     a=group:BUNDLE 0\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111\n\
+    ///     i=main audio\n\
+    ///     c=IN IP4 0.0.0.0\n\
+    ///     b=AS:64\n\
+    ///     a=mid:0\n\
+    ///     a=sendrecv\n\
+    ///     a=rtpmap:111 opus/48000/2\n\
+    ///     a=fmtp:111 minptime=10;useinbandfec=1\n\
+    ///     a=ptime:20\n"
+    /// );
+    ///
+    /// assert_eq!(sdp.to_string(), text.replace('\n', "\r\n"));
+    ///
+    /// let value = "v=0\r\n\
+    ///     s=-\r\n\
+    ///     t=0 0\r\n\
+    ///     a=group:BUNDLE 0\r\n\
+    ///     a=msid-semantic: WMS *\r\n\
+    ///     m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+    ///     a=rtcp-mux\r\n\
+    ///     a=lang:de\r\n\
+    ///     a=lang:fr\r\n\
+    ///     a=rtpmap:111 opus/48000/2\r\n\
+    ///     a=fmtp:111 useinbandfec=1;minptime=10\r\n\
+    ///     a=rtpmap:0 PCMU/8000\r\n";
+    ///
+    /// assert_eq!(Sdp::try_from(value).unwrap().to_string(), value);
+    /// ```
+    pub fn encode<W: fmt::Write>(&self, sink: &mut W, ending: LineEnding) -> fmt::Result {
+        Writer::new(sink, ending).sdp(self)
+    }
+
+    /// write to an io sink.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use sdp::writer::LineEnding;
+    /// use sdp::Sdp;
+    /// use std::convert::*;
+    ///
+    /// let sdp = Sdp::try_from("v=0\r\ns=-\r\nt=0 0\r\n").unwrap();
+    /// let mut buf = Vec::new();
+    /// sdp.write_to(&mut buf, LineEnding::Crlf).unwrap();
+    /// assert_eq!(buf, b"v=0\r\ns=-\r\nt=0 0\r\n");
+    /// ```
+    pub fn write_to<W: io::Write>(&self, sink: &mut W, ending: LineEnding) -> io::Result<()> {
+        let mut sink = IoSink {
+            inner: sink,
+            error: None
+        };
+
+        self.encode(&mut sink, ending).map_err(|_| {
+            sink.error
+                .take()
+                .unwrap_or_else(|| io::Error::other("sdp encode failed!"))
+        })
+    }
+}
+
+impl<'a> fmt::Display for Sdp<'a> {
+    /// the session description with CRLF line endings.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.encode(f, LineEnding::Crlf)
+    }
+}
@@ -22,20 +22,21 @@ use std::convert::{
 #[derive(PartialEq, Eq)]
 #[derive(Copy, Clone, Debug)]
 pub enum Kind {
-    TryAlternate = 0x0300,
-    BadRequest = 0x0400,
-    Unauthorized = 0x0401,
-    Forbidden = 0x0403,
-    RequestTimedout = 0x0408,
-    UnknownAttribute = 0x0420,
-    AllocationMismatch = 0x0437,
-    StaleNonce = 0x0438,
-    AddressFamilyNotSupported = 0x0440,
-    WrongCredentials = 0x0441,
-    UnsupportedTransportAddress = 0x0442,
-    AllocationQuotaReached = 0x0486,
-    ServerError = 0x0500,
-    InsufficientCapacity = 0x0508,
+    TryAlternate = 300,
+    BadRequest = 400,
+    Unauthorized = 401,
+    Forbidden = 403,
+    RequestTimedout = 408,
+    UnknownAttribute = 420,
+    AllocationMismatch = 437,
+    StaleNonce = 438,
+    AddressFamilyNotSupported = 440,
+    WrongCredentials = 441,
+    UnsupportedTransportAddress = 442,
+    PeerAddressFamilyMismatch = 443,
+    AllocationQuotaReached = 486,
+    ServerError = 500,
+    InsufficientCapacity = 508,
 }
 
 /// stun message error attribute. 
@@ -64,6 +65,9 @@ impl Error<'_> {
 
     /// encode the error type as bytes.
     ///
+    /// the hundreds digit of the code is the class,
+    /// the code modulo 100 is the number.
+    ///
     /// # Unit Test
     ///
     /// ```
@@ -82,10 +86,15 @@ impl Error<'_> {
     /// let error = Error::from(ErrKind::TryAlternate);
     /// error.into(&mut buf);
     /// assert_eq!(&buf[..], &buffer);
+    ///
+    /// let mut buf = BytesMut::with_capacity(1280);
+    /// Error::from(ErrKind::StaleNonce).into(&mut buf);
+    /// assert_eq!(&buf[..4], &[0x00, 0x00, 0x04, 0x26]);
     /// ```
     pub fn into(self, buf: &mut BytesMut) {
         buf.put_u16(0x0000);
-        buf.put_u16(self.code);
+        buf.put_u8((self.code / 100) as u8);
+        buf.put_u8((self.code % 100) as u8);
         buf.put(self.message.as_bytes());
     }
 }
@@ -109,13 +118,17 @@ impl<'a> TryFrom<&'a [u8]> for Error<'a> {
     /// let error = Error::try_from(&buffer[..]).unwrap();
     /// assert_eq!(error.code, ErrKind::TryAlternate as u16);
     /// assert_eq!(error.message, "Try Alternate");
+    ///
+    /// let error = Error::try_from(&[0x00, 0x00, 0x04, 0x26][..]).unwrap();
+    /// assert_eq!(error.code, 438);
     /// ```
     #[rustfmt::skip]
     fn try_from(packet: &'a [u8]) -> Result<Self, Self::Error> {
         ensure!(packet.len() >= 4, "buffer len < 4");
         ensure!(util::as_u16(&packet[..2]) == 0x0000, "missing reserved");
+        ensure!((3..=6).contains(&packet[2]) && packet[3] < 100, "invalid code");
         Ok(Self { 
-            code: util::as_u16(&packet[2..4]),
+            code: packet[2] as u16 * 100 + packet[3] as u16,
             message: std::str::from_utf8(&packet[4..])?,
         })
     }
@@ -144,6 +157,7 @@ impl Into<&'static str> for Kind {
             Self::AddressFamilyNotSupported => "Address Family not Supported",
             Self::WrongCredentials => "Wrong Credentials",
             Self::UnsupportedTransportAddress => "Unsupported Transport Address",
+            Self::PeerAddressFamilyMismatch => "Peer Address Family Mismatch",
             Self::AllocationQuotaReached => "Allocation Quota Reached",
             Self::ServerError => "Server Error",
             Self::InsufficientCapacity => "Insufficient Capacity",
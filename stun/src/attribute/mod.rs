@@ -4,7 +4,12 @@ mod error;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 use std::net::SocketAddr;
-pub use address::Addr;
+pub use address::{
+    Addr,
+    FAMILY_IPV4,
+    FAMILY_IPV6
+};
+use anyhow::ensure;
 use crate::util;
 use bytes::{
     BytesMut,
@@ -30,13 +35,19 @@ pub enum AttrKind {
     XorMappedAddress = 0x0020,
     MappedAddress = 0x0001,
     ResponseOrigin = 0x802B,
+    AlternateServer = 0x8023,
     Software = 0x8022,
     MessageIntegrity = 0x0008,
+    MessageIntegritySha256 = 0x001C,
     ErrorCode = 0x0009,
     Lifetime = 0x000D,
     ReqeestedTransport = 0x0019,
+    RequestedAddressFamily = 0x0017,
+    EvenPort = 0x0018,
+    ReservationToken = 0x0022,
     Fingerprint = 0x8028,
     ChannelNumber = 0x000C,
+    UnknownAttributes = 0x000A,
 }
 
 /// dyn stun/turn message attribute.
@@ -229,6 +240,47 @@ impl<'a> Property<'a> for MessageIntegrity {
     }
 }
 
+/// The MESSAGE-INTEGRITY-SHA256 attribute contains an HMAC-SHA256
+/// [RFC2104] of the STUN message.  The MESSAGE-INTEGRITY-SHA256
+/// attribute can be present in any STUN message type.  The MESSAGE-
+/// INTEGRITY-SHA256 attribute contains an initial portion of the HMAC-
+/// SHA-256 [RFC2104] of the STUN message.  The value will be at most 32
+/// bytes, but it MUST be at least 16 bytes and MUST be a multiple of 4
+/// bytes.  The value must be the full 32 bytes unless the STUN Usage
+/// explicitly specifies that truncation is allowed.  STUN Usages may
+/// specify a minimum length longer than 16 bytes.
+///
+/// The key for the HMAC depends on which credential mechanism is in use.
+/// Section 9.1.1 defines the key for the short-term credential
+/// mechanism, and Section 9.2.2 defines the key for the long-term
+/// credential mechanism.  Other credential mechanism MUST define the key
+/// that is used for the HMAC.
+///
+/// The text used as input to HMAC is the STUN message, up to and
+/// including the attribute preceding the MESSAGE-INTEGRITY-SHA256
+/// attribute.  The Length field of the STUN message header is adjusted
+/// to point to the end of the MESSAGE-INTEGRITY-SHA256 attribute.  The
+/// value of the MESSAGE-INTEGRITY-SHA256 attribute is set to a dummy
+/// value.
+pub struct MessageIntegritySha256;
+impl<'a> Property<'a> for MessageIntegritySha256 {
+    type Inner = &'a [u8];
+    type Error = anyhow::Error;
+    fn kind() -> AttrKind {
+        AttrKind::MessageIntegritySha256
+    }
+
+    fn into(value: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
+        buf.put(value);
+    }
+
+    fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!((16..=32).contains(&buf.len()), "invalid len");
+        ensure!(util::pad_size(buf.len()) == 0, "invalid len");
+        Ok(buf)
+    }
+}
+
 /// The XOR-PEER-ADDRESS specifies the address and port of the peer as
 /// seen from the TURN server.  (For example, the peer's server-reflexive
 /// transport address if the peer is behind a NAT.)  It is encoded in the
@@ -401,6 +453,28 @@ impl<'a> Property<'a> for ResponseOrigin {
     }
 }
 
+/// The alternate server represents an alternate transport address
+/// identifying a different STUN server that the STUN client should try.
+///
+/// It is encoded in the same way as MAPPED-ADDRESS and thus refers to a
+/// single server by IP address.
+pub struct AlternateServer;
+impl<'a> Property<'a> for AlternateServer {
+    type Inner = SocketAddr;
+    type Error = anyhow::Error;
+    fn kind() -> AttrKind {
+        AttrKind::AlternateServer
+    }
+
+    fn into(value: Self::Inner, buf: &mut BytesMut, token: &[u8]) {
+        Addr::into(&value, token, buf, false)
+    }
+
+    fn try_from(buf: &'a [u8], token: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        Addr::try_from(buf, token, false)
+    }
+}
+
 /// The ERROR-CODE attribute is used in error response messages.  It
 /// contains a numeric error code value in the range of 300 to 699 plus a
 /// textual reason phrase encoded in UTF-8 [RFC3629]; it is also
@@ -457,6 +531,7 @@ impl<'a> Property<'a> for Lifetime {
     }
 
     fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(buf.len() >= 4, "buf len < 4");
         Ok(util::as_u32(buf))
     }
 }
@@ -489,15 +564,165 @@ impl<'a> Property<'a> for ReqeestedTransport {
         AttrKind::ReqeestedTransport
     }
 
-    fn into(_: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
-        buf.put_u8(0x11)
+    fn into(value: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
+        buf.put_u8(value);
+        buf.put(&[0u8; 3][..]);
+    }
+
+    fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(buf.len() >= 4, "buf len < 4");
+        Ok(buf[0])
+    }
+}
+
+/// This attribute is used in Allocate and Refresh requests to specify
+/// the address type requested by the client.  The value of this
+/// attribute is 4 bytes with the following format:
+///
+/// ```bash
+///   0                   1                   2                   3
+///   0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+///  +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///  |     Family    |            Reserved                           |
+///  +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+///
+/// Family:  there are two values defined for this field and specified in
+///    Section 14.1 of [RFC8489]: 0x01 for IPv4 addresses and 0x02 for IPv6
+///    addresses.
+///
+/// Reserved:  at this point, the 24 bits in the Reserved field MUST be
+///    set to zero by the client and MUST be ignored by the server.
+///
+/// # Unit Test
+///
+/// ```
+/// use stun::attribute::*;
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+/// <RequestedAddressFamily as Property>::into(FAMILY_IPV6, &mut buf, &[]);
+/// assert_eq!(&buf[..], &[0x02, 0x00, 0x00, 0x00]);
+///
+/// let family = <RequestedAddressFamily as Property>::try_from(&buf[..], &[]);
+/// assert_eq!(family.unwrap(), FAMILY_IPV6);
+/// assert!(<RequestedAddressFamily as Property>::try_from(&buf[..1], &[]).is_err());
+///
+/// let invalid = [0x03, 0x00, 0x00, 0x00];
+/// assert!(<RequestedAddressFamily as Property>::try_from(&invalid[..], &[]).is_err());
+/// ```
+pub struct RequestedAddressFamily;
+impl<'a> Property<'a> for RequestedAddressFamily {
+    type Inner = u8;
+    type Error = anyhow::Error;
+    fn kind() -> AttrKind {
+        AttrKind::RequestedAddressFamily
+    }
+
+    fn into(value: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
+        buf.put_u8(value);
+        buf.put(&[0u8; 3][..]);
     }
 
     fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(buf.len() >= 4, "buf len < 4");
+        ensure!(buf[0] == FAMILY_IPV4 || buf[0] == FAMILY_IPV6, "invalid family");
         Ok(buf[0])
     }
 }
 
+/// This attribute allows the client to request that the port in the
+/// relayed transport address be even and (optionally) that the server
+/// reserve the next-higher port number.  The value portion of this
+/// attribute is 1 byte long.  Its format is:
+///
+/// ```bash
+///   0
+///   0 1 2 3 4 5 6 7
+///  +-+-+-+-+-+-+-+-+
+///  |R|    RFFU     |
+///  +-+-+-+-+-+-+-+-+
+/// ```
+///
+/// R: If 1, the server is requested to reserve the next-higher port
+///    number (on the same IP address) for a subsequent allocation.  If
+///    0, no such reservation is requested.
+///
+/// RFFU:  Reserved For Future Use.
+///
+/// The other 7 bits of the attribute's value must be set to zero on
+/// transmission and ignored on reception.
+///
+/// # Unit Test
+///
+/// ```
+/// use stun::attribute::*;
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+/// <EvenPort as Property>::into(true, &mut buf, &[]);
+/// assert_eq!(&buf[..], &[0x80]);
+/// assert!(<EvenPort as Property>::try_from(&[0xff], &[]).unwrap());
+/// assert!(!<EvenPort as Property>::try_from(&[0x7f], &[]).unwrap());
+/// ```
+pub struct EvenPort;
+impl<'a> Property<'a> for EvenPort {
+    type Inner = bool;
+    type Error = anyhow::Error;
+    fn kind() -> AttrKind {
+        AttrKind::EvenPort
+    }
+
+    fn into(value: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
+        buf.put_u8(if value { 0x80 } else { 0x00 });
+    }
+
+    fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(!buf.is_empty(), "buf is empty");
+        Ok(buf[0] & 0x80 != 0)
+    }
+}
+
+/// The RESERVATION-TOKEN attribute contains a token that uniquely
+/// identifies a relayed transport address being held in reserve by the
+/// server.  The server includes this attribute in a success response to
+/// tell the client about the token, and the client includes this
+/// attribute in a subsequent Allocate request to request the server use
+/// that relayed transport address for the allocation.
+///
+/// The attribute value is 8 bytes and contains the token value.
+///
+/// # Unit Test
+///
+/// ```
+/// use stun::attribute::*;
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+/// <ReservationToken as Property>::into(0x0102030405060708, &mut buf, &[]);
+/// assert_eq!(&buf[..], &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+///
+/// let token = <ReservationToken as Property>::try_from(&buf[..], &[]);
+/// assert_eq!(token.unwrap(), 0x0102030405060708);
+/// ```
+pub struct ReservationToken;
+impl<'a> Property<'a> for ReservationToken {
+    type Inner = u64;
+    type Error = anyhow::Error;
+    fn kind() -> AttrKind {
+        AttrKind::ReservationToken
+    }
+
+    fn into(value: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
+        buf.put_u64(value);
+    }
+
+    fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(buf.len() >= 8, "buf len < 8");
+        Ok(util::as_u64(buf))
+    }
+}
+
 /// The FINGERPRINT attribute MAY be present in all STUN messages.
 /// 
 /// The value of the attribute is computed as the CRC-32 of the STUN
@@ -542,6 +767,7 @@ impl<'a> Property<'a> for Fingerprint {
     }
 
     fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(buf.len() >= 4, "buf len < 4");
         Ok(util::as_u32(buf))
     }
 }
@@ -572,6 +798,48 @@ impl<'a> Property<'a> for ChannelNumber {
     }
 
     fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        ensure!(buf.len() >= 4, "buf len < 4");
         Ok(util::as_u16(buf))
     }
 }
+
+/// The UNKNOWN-ATTRIBUTES attribute is present only in an error response
+/// when the response code in the ERROR-CODE attribute is 420 (Unknown
+/// Attribute).
+/// 
+/// The attribute contains a list of 16-bit values, each of which
+/// represents an attribute type that was not understood by the server.
+/// 
+/// ```bash
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |      Attribute 1 Type         |       Attribute 2 Type        |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |      Attribute 3 Type         |       Attribute 4 Type    ...
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+/// 
+/// Note: In [RFC3489], this field was padded to 32 by duplicating the
+/// last attribute.  In this version of the specification, the normal
+/// padding rules for attributes are used instead.
+pub struct UnknownAttributes;
+impl<'a> Property<'a> for UnknownAttributes {
+    type Inner = Vec<u16>;
+    type Error = anyhow::Error;
+    fn kind() -> AttrKind {
+        AttrKind::UnknownAttributes
+    }
+
+    fn into(value: Self::Inner, buf: &mut BytesMut, _: &[u8]) {
+        for kind in value {
+            buf.put_u16(kind)
+        }
+    }
+
+    fn try_from(buf: &'a [u8], _: &'a [u8]) -> Result<Self::Inner, Self::Error> {
+        let kinds = buf.chunks_exact(2);
+        ensure!(kinds.remainder().is_empty(), "invalid len");
+        Ok(kinds.map(util::as_u16).collect())
+    }
+}
@@ -3,8 +3,35 @@ use anyhow::ensure;
 use super::util;
 
 /// channel data message.
+///
+/// ```bash
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |         Channel Number        |            Length             |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                                                               |
+/// /                       Application Data                        /
+/// /                                                               /
+/// |                                                               |
+/// |                               +-------------------------------+
+/// |                               |
+/// +-------------------------------+
+/// ```
+///
+/// The Channel Number field specifies the number of the channel on which
+/// the data is traveling, and thus, the address of the peer that is
+/// sending or is to receive the data.  The channel number MUST be in
+/// the range 0x4000 through 0x4FFF.
+///
+/// The Length field specifies the length in bytes of the application
+/// data field (i.e., it does not include the size of the ChannelData
+/// header).  Note that 0 is a valid length.
+///
+/// over UDP the padding is not required, the bytes following
+/// the application data are not part of the message.
 pub struct ChannelData<'a> {
-    /// channnel data bytes.
+    /// channnel data bytes, header included.
     pub buf: &'a [u8],
     /// channel number.
     pub number: u16,
@@ -19,11 +46,25 @@ impl<'a> TryFrom<&'a [u8]> for ChannelData<'a> {
     /// use std::convert::TryFrom;
     /// 
     /// let buffer: [u8; 4] = [
-    ///     0x00, 0x01, 0x00, 0x00
+    ///     0x40, 0x01, 0x00, 0x00
     /// ];
     ///         
     /// let data = ChannelData::try_from(&buffer[..]).unwrap();
-    /// assert_eq!(data.number, 1);
+    /// assert_eq!(data.number, 0x4001);
+    ///
+    /// let buffer: [u8; 8] = [
+    ///     0x40, 0x01, 0x00, 0x02,
+    ///     0x01, 0x02, 0x00, 0x00
+    /// ];
+    ///
+    /// let data = ChannelData::try_from(&buffer[..]).unwrap();
+    /// assert_eq!(data.buf, &buffer[..6]);
+    ///
+    /// let buffer: [u8; 4] = [
+    ///     0x50, 0x00, 0x00, 0x00
+    /// ];
+    ///
+    /// assert!(ChannelData::try_from(&buffer[..]).is_err());
     /// ```
     #[rustfmt::skip]
     fn try_from(buf: &'a [u8]) -> Result<Self, Self::Error> {
@@ -32,6 +73,7 @@ impl<'a> TryFrom<&'a [u8]> for ChannelData<'a> {
         let size = util::as_u16(&buf[2..4]) as usize;
         ensure!(size <= len - 4, "data body len < size");
         let number = util::as_u16(&buf[..2]);
-        Ok(Self { buf, number })
+        ensure!((0x4000..=0x4FFF).contains(&number), "invalid channel number");
+        Ok(Self { buf: &buf[..size + 4], number })
     }
 }
@@ -6,6 +6,7 @@ use anyhow::{
 };
 
 use super::attribute::{
+    MessageIntegritySha256,
     MessageIntegrity,
     AttrKind,
     Property
@@ -37,8 +38,14 @@ pub struct MessageReader<'a> {
     raw: &'a [u8],
     /// message valid block bytes size.
     valid_offset: u16,
+    /// the MessageIntegritySha256 attribute offset.
+    sha256_offset: Option<u16>,
     // message attribute list.
     attributes: Vec<(AttrKind, &'a [u8])>,
+    // unknown comprehension-required attribute types.
+    unknown: Vec<u16>,
+    // whether the message carries a valid Fingerprint.
+    fingerprint: bool,
 }
 
 /// stun message writer.
@@ -87,6 +94,47 @@ impl<'a> MessageWriter<'a> {
         }
     }
     
+    /// create a message for a new transaction.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use stun::*;
+    /// use bytes::BytesMut;
+    /// use std::convert::TryFrom;
+    ///
+    /// let token = [
+    ///     0x72, 0x6d, 0x49, 0x42,
+    ///     0x72, 0x52, 0x64, 0x48,
+    ///     0x57, 0x62, 0x4b, 0x2b
+    /// ];
+    ///
+    /// let mut buf = BytesMut::new();
+    /// MessageWriter::new(Kind::BindingRequest, &token, &mut buf)
+    ///     .try_into(None)
+    ///     .unwrap();
+    ///
+    /// let message = MessageReader::try_from(&buf[..]).unwrap();
+    /// assert_eq!(message.kind, Kind::BindingRequest);
+    /// assert_eq!(message.token, &token);
+    /// ```
+    #[rustfmt::skip]
+    pub fn new(
+        kind: Kind,
+        token: &'a [u8; 12],
+        raw: &'a mut BytesMut
+    ) -> Self {
+        raw.clear();
+        raw.put_u16(kind as u16);
+        raw.put_u16(0);
+        raw.put(&COOKIE[..]);
+        raw.put(&token[..]);
+        Self {
+            raw,
+            token,
+        }
+    }
+
     /// append attribute.
     ///
     /// append attribute to message attribute list.
@@ -180,7 +228,7 @@ impl<'a> MessageWriter<'a> {
     ///     0x9c, 0x5c, 0x19, 0x17,
     ///     0x98, 0xc6, 0xd9, 0xde, 
     ///     0x80, 0x28, 0x00, 0x04,
-    ///     0xed, 0x41, 0xb6, 0xbe
+    ///     0x4a, 0xa5, 0xab, 0x56
     /// ];
     /// 
     /// let mut buf = BytesMut::with_capacity(1280);
@@ -237,7 +285,7 @@ impl<'a> MessageWriter<'a> {
     ///     0x9c, 0x5c, 0x19, 0x17,
     ///     0x98, 0xc6, 0xd9, 0xde, 
     ///     0x80, 0x28, 0x00, 0x04,
-    ///     0xed, 0x41, 0xb6, 0xbe
+    ///     0x4a, 0xa5, 0xab, 0x56
     /// ];
     /// 
     /// let mut buf = BytesMut::from(&buffer[..]);
@@ -252,7 +300,7 @@ impl<'a> MessageWriter<'a> {
         
         // compute new size,
         // new size include the MessageIntegrity attribute size.
-        let mut buf_size = (self.raw.len() + 4) as u16;
+        let buf_size = (self.raw.len() + 4) as u16;
         let size_buf = buf_size.to_be_bytes();
 
         // overwrite old size with new size.
@@ -270,25 +318,215 @@ impl<'a> MessageWriter<'a> {
         self.raw.put_u16(20);
         self.raw.put(property_buf);
 
+        self.fingerprint();
+        Ok(())
+    }
+
+    /// append MessageIntegritySha256 attribute.
+    ///
+    /// write the attribute list size, add the `MessageIntegritySha256`
+    /// attribute and the `Fingerprint` attribute to the stun message.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use stun::*;
+    /// use stun::attribute::UserName;
+    /// use bytes::BytesMut;
+    /// use std::convert::TryFrom;
+    ///
+    /// let token = [
+    ///     0x72, 0x6d, 0x49, 0x42,
+    ///     0x72, 0x52, 0x64, 0x48,
+    ///     0x57, 0x62, 0x4b, 0x2b
+    /// ];
+    ///
+    /// let key = util::long_key_sha256("panda", "panda", "raspberry");
+    /// let mut buf = BytesMut::new();
+    /// let mut message = MessageWriter::new(Kind::BindingRequest, &token, &mut buf);
+    /// message.append::<UserName>("panda");
+    /// message.try_into_sha256(&key).unwrap();
+    /// assert_eq!(buf.len(), 20 + 12 + 36 + 8);
+    ///
+    /// let message = MessageReader::try_from(&buf[..]).unwrap();
+    /// assert!(message.integrity_sha256(&key).is_ok());
+    /// assert!(message.integrity_sha256(&key[..16]).is_err());
+    /// ```
+    pub fn try_into_sha256(&mut self, key: &[u8]) -> Result<()> {
+        self.try_into_sha256_truncated(key, 32)
+    }
+
+    /// append truncated MessageIntegritySha256 attribute.
+    ///
+    /// The value will be at most 32 bytes, but it MUST be at least 16
+    /// bytes and MUST be a multiple of 4 bytes.  The value must be the
+    /// full 32 bytes unless the STUN Usage explicitly specifies that
+    /// truncation is allowed.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use stun::*;
+    /// use stun::attribute::UserName;
+    /// use bytes::BytesMut;
+    /// use std::convert::TryFrom;
+    ///
+    /// let token = [
+    ///     0x72, 0x6d, 0x49, 0x42,
+    ///     0x72, 0x52, 0x64, 0x48,
+    ///     0x57, 0x62, 0x4b, 0x2b
+    /// ];
+    ///
+    /// let key = util::long_key_sha256("panda", "panda", "raspberry");
+    /// let mut buf = BytesMut::new();
+    /// let mut message = MessageWriter::new(Kind::BindingRequest, &token, &mut buf);
+    /// message.append::<UserName>("panda");
+    /// message.try_into_sha256_truncated(&key, 16).unwrap();
+    /// assert_eq!(buf.len(), 20 + 12 + 20 + 8);
+    ///
+    /// let message = MessageReader::try_from(&buf[..]).unwrap();
+    /// assert!(message.integrity_sha256(&key).is_ok());
+    /// assert!(message.integrity_sha256(&key[..16]).is_err());
+    ///
+    /// let mut buf = BytesMut::new();
+    /// let mut message = MessageWriter::new(Kind::BindingRequest, &token, &mut buf);
+    /// assert!(message.try_into_sha256_truncated(&key, 18).is_err());
+    /// assert!(message.try_into_sha256_truncated(&key, 36).is_err());
+    /// ```
+    #[rustfmt::skip]
+    pub fn try_into_sha256_truncated(&mut self, key: &[u8], size: usize) -> Result<()> {
+        assert!(self.raw.len() >= 20);
+        ensure!((16..=32).contains(&size), "invalid len");
+        ensure!(util::pad_size(size) == 0, "invalid len");
+
         // compute new size,
-        // new size include the Fingerprint attribute size.
-        buf_size += 8;
-        let size_buf = buf_size.to_be_bytes();
+        // new size include the MessageIntegritySha256 attribute size.
+        let size_buf = ((self.raw.len() - 20 + 4 + size) as u16).to_be_bytes();
+        self.raw[2] = size_buf[0];
+        self.raw[3] = size_buf[1];
 
-        // overwrite old size with new size.
+        // digest the message buffer,
+        // write MessageIntegritySha256 attribute.
+        let hmac_output = util::hmac_sha256(key, vec![&self.raw])?.into_bytes();
+        self.raw.put_u16(AttrKind::MessageIntegritySha256 as u16);
+        self.raw.put_u16(size as u16);
+        self.raw.put(&hmac_output[..size]);
+
+        self.fingerprint();
+        Ok(())
+    }
+
+    /// append Fingerprint attribute.
+    ///
+    /// the attribute list size is updated to include the
+    /// attribute before the CRC is computed, so it must be
+    /// the last attribute.  the message integrity methods
+    /// already append it, this is for the messages that are
+    /// sent without integrity.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use stun::*;
+    /// use bytes::BytesMut;
+    /// use std::convert::TryFrom;
+    ///
+    /// let token = [
+    ///     0x72, 0x6d, 0x49, 0x42,
+    ///     0x72, 0x52, 0x64, 0x48,
+    ///     0x57, 0x62, 0x4b, 0x2b
+    /// ];
+    ///
+    /// let mut buf = BytesMut::new();
+    /// let mut message = MessageWriter::new(Kind::BindingRequest, &token, &mut buf);
+    /// message.try_into(None).unwrap();
+    /// message.fingerprint();
+    /// assert_eq!(&buf[20..24], &[0x80, 0x28, 0x00, 0x04]);
+    ///
+    /// let message = MessageReader::try_from(&buf[..]).unwrap();
+    /// assert!(message.fingerprint());
+    ///
+    /// buf[27] ^= 0xff;
+    /// assert!(MessageReader::try_from(&buf[..]).is_err());
+    /// ```
+    #[rustfmt::skip]
+    pub fn fingerprint(&mut self) {
+        let size_buf = ((self.raw.len() - 20 + 8) as u16).to_be_bytes();
         self.raw[2] = size_buf[0];
         self.raw[3] = size_buf[1];
 
-        // CRC Fingerprint
+        // CRC Fingerprint,
+        // computed over the message preceding the attribute.
+        let crc = util::fingerprint(&self.raw);
         self.raw.put_u16(AttrKind::Fingerprint as u16);
         self.raw.put_u16(4);
-        self.raw.put_u32(util::fingerprint(&self.raw));
-
-        Ok(())
+        self.raw.put_u32(crc);
     }
 }
 
 impl<'a> MessageReader<'a> {
+    /// get unknown comprehension-required attributes.
+    ///
+    /// the attribute types in the range 0x0000-0x7FFF that are not
+    /// supported, a request containing them must be rejected with a
+    /// 420 (Unknown Attribute) error.  unknown comprehension-optional
+    /// attributes are ignored.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use stun::*;
+    /// use stun::attribute::*;
+    /// use std::convert::TryFrom;
+    ///
+    /// let buffer = [
+    ///     0x00u8, 0x01, 0x00, 0x18,
+    ///     0x21, 0x12, 0xa4, 0x42,
+    ///     0x72, 0x6d, 0x49, 0x42,
+    ///     0x72, 0x52, 0x64, 0x48,
+    ///     0x57, 0x62, 0x4b, 0x2b,
+    ///     0x00, 0x30, 0x00, 0x01,
+    ///     0x01, 0x00, 0x00, 0x00,
+    ///     0x80, 0x30, 0x00, 0x00,
+    ///     0x00, 0x06, 0x00, 0x05,
+    ///     0x70, 0x61, 0x6e, 0x64,
+    ///     0x61, 0x00, 0x00, 0x00
+    /// ];
+    ///
+    /// let message = MessageReader::try_from(&buffer[..]).unwrap();
+    /// assert_eq!(message.unknown(), &[0x0030]);
+    /// assert_eq!(message.get::<UserName>().unwrap().unwrap(), "panda");
+    /// ```
+    pub fn unknown(&self) -> &[u16] {
+        &self.unknown
+    }
+
+    /// whether the message carries a Fingerprint.
+    ///
+    /// the Fingerprint attribute is checked while decoding,
+    /// a message with a mismatched CRC is rejected.
+    ///
+    /// # Unit Test
+    ///
+    /// ```
+    /// use stun::*;
+    /// use std::convert::TryFrom;
+    ///
+    /// let buffer = [
+    ///     0x00u8, 0x01, 0x00, 0x00,
+    ///     0x21, 0x12, 0xa4, 0x42,
+    ///     0x72, 0x6d, 0x49, 0x42,
+    ///     0x72, 0x52, 0x64, 0x48,
+    ///     0x57, 0x62, 0x4b, 0x2b
+    /// ];
+    ///
+    /// let message = MessageReader::try_from(&buffer[..]).unwrap();
+    /// assert!(!message.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> bool {
+        self.fingerprint
+    }
+
     /// get attribute.
     ///
     /// get attribute from message attribute list.
@@ -393,6 +631,39 @@ impl<'a> MessageReader<'a> {
 
         Ok(())
     }
+
+    /// check MessageIntegritySha256 attribute.
+    ///
+    /// return whether the `MessageIntegritySha256` attribute
+    /// contained in the message can pass the check, a truncated
+    /// value is compared with the leading bytes of the digest.
+    /// see `MessageWriter::try_into_sha256`.
+    #[rustfmt::skip]
+    pub fn integrity_sha256(&self, key: &[u8]) -> Result<()> {
+        let offset = self.sha256_offset
+            .ok_or_else(|| anyhow!("not found MessageIntegritySha256"))? as usize;
+        let integrity = self
+            .get::<MessageIntegritySha256>()
+            .ok_or_else(|| anyhow!("not found MessageIntegritySha256"))??;
+
+        // the size points to the end of the attribute,
+        // the value can be truncated.
+        let size_buf = ((offset - 20 + 4 + integrity.len()) as u16).to_be_bytes();
+        let body = vec![
+            &self.raw[0..2],
+            &size_buf,
+            &self.raw[4..offset]
+        ];
+
+        // digest the message buffer,
+        // compare local and original attribute.
+        let hmac_output = util::hmac_sha256(key, body)?.into_bytes();
+        if integrity != &hmac_output[..integrity.len()] {
+            return Err(anyhow!("assert fail!"))
+        }
+
+        Ok(())
+    }
 }
 
 impl<'a> TryFrom<&'a [u8]> for MessageReader<'a> {
@@ -419,9 +690,11 @@ impl<'a> TryFrom<&'a [u8]> for MessageReader<'a> {
     fn try_from(buf: &'a [u8]) -> Result<Self, Self::Error> {
         ensure!(buf.len() >= 20, "message len < 20");
         let mut attributes = Vec::with_capacity(6);
+        let mut unknown = Vec::new();
         let mut find_valid_offset = false;
         let mut valid_offset = 0;
-        let count_size = buf.len();
+        let mut sha256_offset = None;
+        let mut fingerprint = false;
 
         // message type
         // message size
@@ -430,15 +703,20 @@ impl<'a> TryFrom<&'a [u8]> for MessageReader<'a> {
         let kind = Kind::try_from(util::as_u16(&buf[..2]))?;
         let size = util::as_u16(&buf[2..4]) as usize;
         ensure!(buf[4..8] == COOKIE[..], "missing cookie");
-        ensure!(count_size >= size + 20, "missing len");
+        ensure!(buf.len() >= size + 20, "missing len");
+        ensure!(util::pad_size(size) == 0, "invalid len");
+
+        // the attributes end with the message,
+        // trailing bytes are ignored.
+        let count_size = size + 20;
 
         // get transaction id
         let token = &buf[8..20];
         let mut offset = 20;
-        
+
     loop {
 
-        // if the buf length is not long enough to continue, 
+        // if the buf length is not long enough to continue,
         // jump out of the loop.
         if count_size - offset < 4 {
             break;
@@ -450,23 +728,22 @@ impl<'a> TryFrom<&'a [u8]> for MessageReader<'a> {
             buf[offset + 1]
         ]);
 
-        // whether the MessageIntegrity attribute has been found, 
+        // whether the MessageIntegrity attribute has been found,
         // if found, record the current offset position.
         if !find_valid_offset {
             valid_offset = offset as u16;
         }
 
-        // check whether the current attribute is MessageIntegrity, 
+        // check whether the current attribute is MessageIntegrity,
         // if it is, mark this attribute has been found.
         if key == AttrKind::MessageIntegrity as u16 {
             find_valid_offset = true;
         }
 
-        // skip the attributes that are not supported.
-        let attrkind = match AttrKind::try_from(key) {
-            Err(_) => continue,
-            Ok(a) => a
-        };
+        // record the first MessageIntegritySha256 attribute offset.
+        if key == AttrKind::MessageIntegritySha256 as u16 && sha256_offset.is_none() {
+            sha256_offset = Some(offset as u16);
+        }
 
         // get attribute size
         let size = u16::from_be_bytes([
@@ -476,22 +753,31 @@ impl<'a> TryFrom<&'a [u8]> for MessageReader<'a> {
 
         // check if the attribute length has overflowed.
         offset += 4;
-        if count_size - offset < size {
-            break;
+        ensure!(count_size - offset >= size, "attribute len overflow");
+
+        // get attribute body,
+        // if there are padding bytes,
+        // skip padding size.
+        let body = &buf[offset..offset + size];
+
+        // the Fingerprint attribute must be the last attribute,
+        // the CRC covers the message up to the attribute.
+        if key == AttrKind::Fingerprint as u16 {
+            ensure!(size == 4 && offset + size == count_size, "invalid fingerprint");
+            let crc = util::fingerprint(&buf[..offset - 4]);
+            ensure!(util::as_u32(body) == crc, "fingerprint mismatch");
+            fingerprint = true;
         }
 
-        // get attribute body
-        // insert attribute to attributes list.
-        attributes.push((attrkind, &buf[
-            offset..
-            offset + size
-        ]));
+        offset = count_size.min(offset + size + util::pad_size(size));
 
-        // if there are padding bytes, 
-        // skip padding size.
-        let psize = util::pad_size(size);
-        if size > 0 {
-            offset += size + psize;
+        // insert attribute to attributes list,
+        // the attributes that are not supported are skipped,
+        // the comprehension-required ones are recorded.
+        match AttrKind::try_from(key) {
+            Ok(a) => attributes.push((a, body)),
+            Err(_) if key < 0x8000 => unknown.push(key),
+            Err(_) => ()
         }
     }
 
@@ -500,7 +786,10 @@ impl<'a> TryFrom<&'a [u8]> for MessageReader<'a> {
             token,
             raw: buf,
             attributes,
+            unknown,
             valid_offset,
+            sha256_offset,
+            fingerprint,
         })
     }
 }
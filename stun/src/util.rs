@@ -1,4 +1,9 @@
 use hmac::crypto_mac::Output;
+use sha2::{
+    Digest,
+    Sha256
+};
+
 use crc::crc32;
 use hmac::{
     NewMac,
@@ -52,6 +57,32 @@ pub fn long_key(username: &str, key: &str, realm: &str) -> [u8; 16] {
     md5::compute([username, realm, key].join(":")).0
 }
 
+/// create long key for SHA-256.
+///
+/// the key of the long-term credential mechanism when the
+/// PASSWORD-ALGORITHM is SHA-256.
+///
+/// > key = SHA-256(username ":" OpaqueString(realm) ":" OpaqueString(password))
+///
+/// ```
+/// let buffer = [
+///     0xa5u8, 0xbf, 0xe0, 0x2c,
+///     0xcd, 0x8b, 0x08, 0xdd,
+///     0x99, 0x54, 0x40, 0xde,
+///     0xbc, 0x9b, 0x4e, 0x69,
+///     0xfb, 0x0a, 0xec, 0x3d,
+///     0x69, 0x0a, 0x5f, 0x47,
+///     0x92, 0x11, 0xfd, 0xf7,
+///     0x89, 0x05, 0x56, 0xe6
+/// ];
+///
+/// let key = stun::util::long_key_sha256("panda", "panda", "raspberry");
+/// assert_eq!(key, buffer);
+/// ```
+pub fn long_key_sha256(username: &str, key: &str, realm: &str) -> [u8; 32] {
+    Sha256::digest([username, realm, key].join(":").as_bytes()).into()
+}
+
 /// HMAC SHA1 digest.
 ///
 /// # Unit Test
@@ -110,6 +141,39 @@ pub fn hmac_sha1(key: &[u8], source: Vec<&[u8]>) -> Result<Output<Hmac<sha1::Sha
     }
 }
 
+/// HMAC SHA256 digest.
+///
+/// # Unit Test
+///
+/// ```
+/// let key = stun::util::long_key_sha256("panda", "panda", "raspberry");
+/// let sign = [
+///     0x98u8, 0xd0, 0x3c, 0x79,
+///     0xec, 0xc4, 0xc1, 0x19,
+///     0xa8, 0x49, 0xe3, 0x20,
+///     0xa6, 0x10, 0x92, 0x34,
+///     0xde, 0x1f, 0x94, 0x6d,
+///     0x89, 0xdd, 0x22, 0x51,
+///     0xf0, 0xa3, 0xb9, 0x61,
+///     0xfb, 0xd3, 0xa7, 0xf0
+/// ];
+///
+/// let hmac_output = stun::util::hmac_sha256(&key, vec![b"panda"]).unwrap().into_bytes();
+/// assert_eq!(hmac_output.as_slice(), &sign);
+/// ```
+pub fn hmac_sha256(key: &[u8], source: Vec<&[u8]>) -> Result<Output<Hmac<Sha256>>> {
+    match Hmac::<Sha256>::new_varkey(key) {
+        Err(_) => Err(anyhow!("new key failde")),
+        Ok(mut mac) => {
+            for buf in source {
+                mac.update(buf);
+            }
+
+            Ok(mac.finalize())
+        }
+    }
+}
+
 /// CRC32 Fingerprint.
 ///
 /// # Unit Test